@@ -0,0 +1,107 @@
+//! Turns every `examples/dayNN/<name>.in` + `<name>.expected` pair into a test case.
+//!
+//! An `.expected` file lists the answers of the example, one part per line, parts without an
+//! answer are left out and lines starting with `#` are comments:
+//!
+//! ```text
+//! part1: 35
+//! part2: 46
+//! ```
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const EXAMPLES_DIR: &str = "examples";
+
+struct Example {
+    day: String,
+    name: String,
+    input: PathBuf,
+    answers: Vec<(u8, String)>,
+}
+
+fn parse_expected(path: &Path) -> Vec<(u8, String)> {
+    let content = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("cannot read {}: {}", path.display(), err));
+    content.lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let part = match line.split_once(':') {
+                Some(("part1", answer)) => (1, answer),
+                Some(("part2", answer)) => (2, answer),
+                _ => panic!("{}: expected `part1: <answer>` or `part2: <answer>`, got `{}`",
+                            path.display(), line),
+            };
+            (part.0, part.1.trim().to_string())
+        })
+        .collect()
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("cannot read {}: {}", dir.display(), err))
+        .map(|entry| entry.expect("directory entry should be readable").path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    entries
+}
+
+fn examples(root: &Path) -> Vec<Example> {
+    if !root.is_dir() {
+        return Vec::new();
+    }
+    let mut examples = Vec::new();
+    for dir in sorted_entries(root).into_iter().filter(|path| path.is_dir()) {
+        let day = dir.file_name().unwrap().to_string_lossy().to_string();
+        let Some(num) = day.strip_prefix("day").filter(|num| num.len() == 2 && num.parse::<u8>().is_ok()) else {
+            panic!("{}: example directories are named dayNN", dir.display());
+        };
+        for expected in sorted_entries(&dir) {
+            match expected.extension().and_then(|ext| ext.to_str()) {
+                Some("expected") => {}
+                Some("in") if !expected.with_extension("expected").exists() => {
+                    println!("cargo:warning={} has no .expected file, skipping it", expected.display());
+                    continue;
+                }
+                _ => continue,
+            }
+            let input = expected.with_extension("in");
+            if !input.exists() {
+                panic!("{} has no matching {}", expected.display(), input.display());
+            }
+            examples.push(Example {
+                day: format!("Day{}", num),
+                name: expected.file_stem().unwrap().to_string_lossy().replace(['-', '.', ' '], "_"),
+                input: input.canonicalize().expect("example input should resolve"),
+                answers: parse_expected(&expected),
+            });
+        }
+    }
+    examples
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", EXAMPLES_DIR);
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join(EXAMPLES_DIR);
+
+    let mut tests = String::new();
+    for example in examples(&root) {
+        for (part, answer) in example.answers {
+            writeln!(tests, "\
+#[test]
+fn {prefix}_{name}_part{part}() {{
+    let input = include_str!({input:?});
+    let answer = super::{day}.run_part{part}(input).unwrap_or_else(|err| panic!(\"{{}}\", err));
+    assert_eq!(answer, {answer:?});
+}}
+",
+                     prefix = example.day.to_lowercase(), day = example.day, name = example.name,
+                     part = part, input = example.input.display().to_string(), answer = answer)
+                .unwrap();
+        }
+    }
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).expect("generated tests should be writable");
+}
@@ -0,0 +1,83 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+
+/// System allocator that counts the allocations made by each thread, counting only happens
+/// once it is installed with `#[global_allocator]`
+pub struct Counting;
+
+thread_local! {
+    static COUNTS: Cell<Allocs> = const { Cell::new(Allocs { count: 0, bytes: 0 }) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // fails only while the thread is being torn down, those allocations are not interesting
+        let _ = COUNTS.try_with(|counts| {
+            let Allocs { count, bytes } = counts.get();
+            counts.set(Allocs { count: count + 1, bytes: bytes + layout.size() });
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Number and total size of allocations
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Allocs {
+    pub count: usize,
+    pub bytes: usize,
+}
+
+impl Display for Allocs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocations ({} bytes)", self.count, self.bytes)
+    }
+}
+
+impl Sum for Allocs {
+    fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
+        iter.fold(Allocs::default(), |acc, allocs| Allocs {
+            count: acc.count + allocs.count,
+            bytes: acc.bytes + allocs.bytes,
+        })
+    }
+}
+
+/// Runs `f` and counts the allocations the current thread made meanwhile
+pub fn count<R>(f: impl FnOnce() -> R) -> (R, Allocs) {
+    let before = COUNTS.with(Cell::get);
+    let result = f();
+    let after = COUNTS.with(Cell::get);
+    (result, Allocs { count: after.count - before.count, bytes: after.bytes - before.bytes })
+}
+
+/// Allocations that copying `input` into a `Vec` of one `String` per line would make, without
+/// making them
+pub fn line_copy(input: &str) -> Allocs {
+    let lines = input.lines().count();
+    Allocs { count: lines + 1, bytes: input.len() + lines * std::mem::size_of::<String>() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn test_count() {
+        let (lines, allocs) = count(|| "ab\ncd".lines().map(str::to_string).collect::<Vec<_>>());
+        assert_eq!(lines.len(), 2);
+        assert!(allocs.count >= 3);
+        assert!(allocs.bytes >= 4);
+        let (_, none) = count(|| "ab\ncd".lines().count());
+        assert_eq!(none, Allocs::default());
+        assert_eq!(line_copy("ab\ncd").count, 3);
+    }
+}
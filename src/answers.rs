@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use serde::Deserialize;
+use crate::err::AocError;
+
+pub const ANSWERS_PATH: &str = "input/answers.toml";
+
+/// Answers may be written either as TOML integers or as strings
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum Value {
+    Int(i64),
+    Str(String),
+}
+
+impl Value {
+    fn answer(&self) -> String {
+        match self {
+            Value::Int(val) => val.to_string(),
+            Value::Str(val) => val.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct Known {
+    part1: Option<Value>,
+    part2: Option<Value>,
+}
+
+/// Locked-in answers keyed by `Solution::DAY`, e.g.
+///
+/// ```toml
+/// [Day01]
+/// part1 = 54605
+/// part2 = "55429"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers(BTreeMap<String, Known>);
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, AocError> {
+        toml::from_str(s)
+            .map(Answers)
+            .map_err(|err| AocError::Config("answers".to_string(), err.to_string()))
+    }
+
+    pub fn load(path: &str) -> Result<Self, AocError> {
+        Answers::parse(&fs::read_to_string(path)?)
+            .map_err(|err| match err {
+                AocError::Config(_, msg) => AocError::Config(path.to_string(), msg),
+                err => err,
+            })
+    }
+
+    pub fn get(&self, day: &str, part: u8) -> Option<String> {
+        let known = self.0.get(day)?;
+        match part {
+            1 => known.part1.as_ref(),
+            2 => known.part2.as_ref(),
+            _ => None,
+        }.map(Value::answer)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+    /// No answer is known and the part failed, so there is nothing to compare
+    Error(String),
+}
+
+impl Verdict {
+    pub fn new(expected: Option<String>, actual: Result<&str, &AocError>) -> Self {
+        match (expected, actual) {
+            (None, Err(err)) => Verdict::Error(err.to_string()),
+            (None, Ok(_)) => Verdict::Missing,
+            (Some(expected), Ok(actual)) if expected == actual => Verdict::Pass,
+            (Some(expected), Ok(actual)) => Verdict::Fail { expected, actual: actual.to_string() },
+            (Some(expected), Err(err)) => Verdict::Fail { expected, actual: err.to_string() },
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected, actual } => write!(f, "FAIL expected {}, got {}", expected, actual),
+            Verdict::Missing => write!(f, "MISSING"),
+            Verdict::Error(err) => write!(f, "ERROR {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse("[Day01]\npart1 = 142\npart2 = \"281\"\n\n[Day02]\npart1 = 8\n").unwrap();
+        assert_eq!(answers.get("Day01", 1).as_deref(), Some("142"));
+        assert_eq!(answers.get("Day01", 2).as_deref(), Some("281"));
+        assert_eq!(answers.get("Day02", 2), None);
+        assert_eq!(answers.get("Day03", 1), None);
+    }
+
+    #[test]
+    fn test_verdict() {
+        assert_eq!(Verdict::new(Some("1".to_string()), Ok("1")), Verdict::Pass);
+        assert_eq!(Verdict::new(None, Ok("1")), Verdict::Missing);
+        let err = AocError::Missing("input".to_string());
+        assert_eq!(Verdict::new(None, Err(&err)), Verdict::Error("Missing input".to_string()));
+        assert_eq!(Verdict::new(Some("1".to_string()), Ok("2")),
+                   Verdict::Fail { expected: "1".to_string(), actual: "2".to_string() });
+    }
+}
@@ -0,0 +1,309 @@
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::hint::black_box;
+use std::io::{ErrorKind, Write};
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use crate::err::AocError;
+use crate::util::{input_path, read_input, DynSolution};
+
+/// How long and how often a benchmark runs
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub warmup: Duration,
+    pub measurement: Duration,
+    /// Most samples to take, slow code takes fewer so they fit the measurement time
+    pub samples: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            warmup: Duration::from_millis(500),
+            measurement: Duration::from_secs(3),
+            samples: 100,
+        }
+    }
+}
+
+impl Config {
+    /// Runs the code exactly once, enough to check a benchmark still works under `cargo test`
+    pub fn smoke() -> Self {
+        Config {
+            warmup: Duration::ZERO,
+            measurement: Duration::ZERO,
+            samples: 1,
+        }
+    }
+}
+
+/// Samples outside Tukey's fences, mild beyond 1.5 and severe beyond 3 interquartile ranges
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+impl Outliers {
+    fn classify(sorted: &[f64]) -> Self {
+        let q1 = percentile(sorted, 0.25);
+        let q3 = percentile(sorted, 0.75);
+        let iqr = q3 - q1;
+        let mut outliers = Outliers::default();
+        for &x in sorted {
+            if x < q1 - 3.0 * iqr {
+                outliers.low_severe += 1;
+            } else if x < q1 - 1.5 * iqr {
+                outliers.low_mild += 1;
+            } else if x > q3 + 3.0 * iqr {
+                outliers.high_severe += 1;
+            } else if x > q3 + 1.5 * iqr {
+                outliers.high_mild += 1;
+            }
+        }
+        outliers
+    }
+
+    pub fn total(&self) -> usize {
+        self.low_severe + self.low_mild + self.high_mild + self.high_severe
+    }
+}
+
+impl Display for Outliers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let counts = [
+            (self.low_severe, "low severe"),
+            (self.low_mild, "low mild"),
+            (self.high_mild, "high mild"),
+            (self.high_severe, "high severe"),
+        ];
+        let parts = counts.iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, kind)| format!("{} {}", count, kind))
+            .collect::<Vec<_>>();
+        write!(f, "outliers: {}", self.total())?;
+        if !parts.is_empty() {
+            write!(f, " ({})", parts.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-iteration timings of a benchmark
+#[derive(Debug)]
+pub struct Stats {
+    pub iterations: u64,
+    pub samples: usize,
+    pub median: Duration,
+    pub min: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub outliers: Outliers,
+}
+
+impl Stats {
+    fn from_samples(samples: &[f64], iterations: u64) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let mean = sorted.iter().sum::<f64>() / sorted.len() as f64;
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>()
+            / (sorted.len().max(2) - 1) as f64;
+        Stats {
+            iterations,
+            samples: sorted.len(),
+            median: Duration::from_secs_f64(percentile(&sorted, 0.5)),
+            min: Duration::from_secs_f64(sorted[0]),
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            outliers: Outliers::classify(&sorted),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "median {:.2?}  min {:.2?}  mean {:.2?} ± {:.2?}  ({} samples × {} iterations, {})",
+               self.median, self.min, self.mean, self.stddev,
+               self.samples, self.iterations, self.outliers)
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Fewest samples slow code is cut down to, even when they overrun the measurement time
+const MIN_SAMPLES: u32 = 10;
+
+/// Warms `f` up, then times up to `config.samples` batches of calls sized so that all of them
+/// fit the measurement time. Code too slow for that gets fewer samples of a single call each,
+/// but at least `MIN_SAMPLES` of them
+pub fn bench<F, R>(config: &Config, mut f: F) -> Stats
+    where F: FnMut() -> R {
+    let start = Instant::now();
+    let mut warmup_runs = 0u32;
+    loop {
+        black_box(f());
+        warmup_runs += 1;
+        if start.elapsed() >= config.warmup {
+            break;
+        }
+    }
+    let per_iteration = start.elapsed() / warmup_runs;
+
+    let fitting = config.measurement.as_nanos() / per_iteration.as_nanos().max(1);
+    let samples = u32::try_from(fitting).unwrap_or(u32::MAX).max(MIN_SAMPLES).min(config.samples).max(1);
+    let per_sample = config.measurement / samples;
+    let iterations = (per_sample.as_nanos() / per_iteration.as_nanos().max(1)).max(1) as u64;
+
+    let timings = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                black_box(f());
+            }
+            start.elapsed().as_secs_f64() / iterations as f64
+        })
+        .collect::<Vec<_>>();
+    Stats::from_samples(&timings, iterations)
+}
+
+/// Benchmarks one part of a day on its default input
+pub fn bench_day(solution: &dyn DynSolution, part: u8, config: &Config) -> Result<Stats, AocError> {
+    let path = input_path(solution.name());
+    let input = read_input(&path)?;
+    solution.bench(&input, part, config).map_err(|err| err.in_file(&path))
+}
+
+pub const HISTORY_PATH: &str = "input/bench_history.jsonl";
+
+/// One benchmarked part as stored in the history file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub commit: String,
+    pub day: String,
+    pub part: u8,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub mean_ns: u64,
+}
+
+impl Record {
+    pub fn new(commit: &str, day: &str, part: u8, stats: &Stats) -> Self {
+        let nanos = |d: Duration| d.as_nanos() as u64;
+        Record {
+            commit: commit.to_string(),
+            day: day.to_string(),
+            part,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+            median_ns: nanos(stats.median),
+            min_ns: nanos(stats.min),
+            mean_ns: nanos(stats.mean),
+        }
+    }
+
+    /// Relative change of the median against an `earlier` record, `0.1` means 10% slower
+    pub fn change(&self, earlier: &Record) -> f64 {
+        self.median_ns as f64 / earlier.median_ns.max(1) as f64 - 1.0
+    }
+}
+
+/// Every recorded bench run, oldest first, stored as one JSON object per line
+#[derive(Debug, Default)]
+pub struct History(Vec<Record>);
+
+impl History {
+    pub fn parse(s: &str) -> Result<Self, AocError> {
+        s.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line)
+                .map_err(|err| AocError::Config("bench history".to_string(), err.to_string())))
+            .collect::<Result<_, _>>()
+            .map(History)
+    }
+
+    /// Loads the history, a missing file is an empty history
+    pub fn load(path: &str) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(s) => History::parse(&s).map_err(|err| match err {
+                AocError::Config(_, msg) => AocError::Config(path.to_string(), msg),
+                err => err,
+            }),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn append(path: &str, records: &[Record]) -> Result<(), AocError> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        for record in records {
+            writeln!(file, "{}", serde_json::to_string(record).expect("record should serialise"))?;
+        }
+        Ok(())
+    }
+
+    /// Most recent record of the given day and part
+    pub fn last(&self, day: &str, part: u8) -> Option<&Record> {
+        self.0.iter().rev().find(|record| record.day == day && record.part == part)
+    }
+}
+
+/// Current commit hash, suffixed with `-dirty` when the work tree has changes
+pub fn git_commit() -> String {
+    let git = |args: &[&str]| Command::new("git").args(args).output().ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    match git(&["rev-parse", "HEAD"]) {
+        Some(hash) if git(&["status", "--porcelain", "--untracked-files=no"])
+            .is_some_and(|status| !status.is_empty()) => format!("{}-dirty", hash),
+        Some(hash) => hash,
+        None => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 4.0, 5.0, 100.0];
+        let stats = Stats::from_samples(&samples, 1);
+        assert_eq!(stats.min, Duration::from_secs(1));
+        assert_eq!(stats.median, Duration::from_secs(3));
+        assert_eq!(stats.outliers, Outliers { high_severe: 1, ..Outliers::default() });
+    }
+
+    #[test]
+    fn test_bench_runs() {
+        let mut calls = 0;
+        let stats = bench(&Config::smoke(), || calls += 1);
+        assert_eq!(stats.samples, 1);
+        assert_eq!(calls as u64, 1 + stats.iterations);
+
+        let config = Config { warmup: Duration::ZERO, measurement: Duration::from_millis(10), samples: 100 };
+        let stats = bench(&config, || std::thread::sleep(Duration::from_millis(2)));
+        assert_eq!((stats.samples, stats.iterations), (MIN_SAMPLES as usize, 1));
+    }
+
+    #[test]
+    fn test_history_last() {
+        let history = History::parse("\
+{\"commit\":\"a\",\"day\":\"Day10\",\"part\":1,\"timestamp\":1,\"median_ns\":100,\"min_ns\":90,\"mean_ns\":110}
+{\"commit\":\"b\",\"day\":\"Day10\",\"part\":2,\"timestamp\":2,\"median_ns\":500,\"min_ns\":400,\"mean_ns\":600}
+{\"commit\":\"c\",\"day\":\"Day10\",\"part\":1,\"timestamp\":3,\"median_ns\":200,\"min_ns\":190,\"mean_ns\":210}
+").unwrap();
+        let last = history.last("Day10", 1).unwrap();
+        assert_eq!(last.commit, "c");
+        assert!(history.last("Day10", 3).is_none());
+        let earlier = history.0[0].clone();
+        assert!((last.change(&earlier) - 1.0).abs() < 1e-9);
+    }
+}
@@ -1,8 +1,499 @@
-use aoc_2023_impl::days::{Day01, Day02, Day03, Day04, Day05, Day06, Day07, Day08, Day09, Day10, Day11, Day12, Day15, Day16};
-#[allow(unused_imports)]
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use aoc_2023_impl::answers::{Answers, Verdict, ANSWERS_PATH};
+use aoc_2023_impl::bench::{self, Config, History, Record, HISTORY_PATH};
+use aoc_2023_impl::client::{self, Client, CONFIG_PATH};
+use aoc_2023_impl::days;
+use aoc_2023_impl::scaffold;
+use aoc_2023_impl::watch::{self, Options};
+use aoc_2023_impl::extract::{self, EXAMPLES_DIR};
+use aoc_2023_impl::{input_path, read_input, AocError, DynSolution};
+use aoc_2023_impl::submit::{Guess, Guesses, Outcome, GUESSES_PATH};
+use aoc_2023_impl::runner::{allocation_summary, run_ordered, timing_table, Report, Run};
+use aoc_2023_impl::alloc::{self, Counting};
 
-use aoc_2023_impl::solve;
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
 
-fn main() {
-    println!("{:?}", solve::<Day16>());
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    /// One JSON object per day and line
+    Json,
+}
+
+
+/// Runs Advent of Code 2023 solutions
+#[derive(Parser, Debug)]
+#[command(version, about, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Benchmark both parts of a day on its puzzle input
+    Bench(BenchArgs),
+    /// Copy the examples and their answers from a saved puzzle page into `examples/dayNN`
+    Extract(ExtractArgs),
+    /// Download the puzzle input of a day into `input/DayNN.in`
+    Fetch(FetchArgs),
+    /// Run one part of a day and submit its answer
+    Submit(SubmitArgs),
+    /// Create and register the module, input and example files of a new day
+    New(NewArgs),
+    /// Re-run a day and its tests whenever its source, input or examples change
+    Watch(WatchArgs),
+}
+
+#[derive(Args, Debug)]
+struct WatchArgs {
+    /// Day to watch, e.g. `16`
+    day: u8,
+
+    /// How often to check the files for changes, in milliseconds
+    #[arg(long, default_value_t = 500)]
+    interval_ms: u64,
+
+    /// Build the day with optimisations when running it
+    #[arg(long)]
+    release: bool,
+
+    /// Give up on a part after this many milliseconds
+    #[arg(long)]
+    timeout_ms: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+struct NewArgs {
+    /// Day to create, e.g. `17`
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+#[derive(Args, Debug)]
+struct SiteArgs {
+    /// Site to talk to instead of `AOC_BASE_URL` or the config file
+    #[arg(long)]
+    base_url: Option<String>,
+
+    /// Config file with the `session` token and `base_url`
+    #[arg(long, default_value = CONFIG_PATH)]
+    config: String,
+}
+
+#[derive(Args, Debug)]
+struct SubmitArgs {
+    /// Day to submit, e.g. `7`
+    day: u8,
+
+    /// Part to submit
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Log of earlier guesses, used to refuse answers known to be wrong
+    #[arg(long, default_value = GUESSES_PATH)]
+    guesses: String,
+
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+#[derive(Args, Debug)]
+struct FetchArgs {
+    /// Day to download, e.g. `17`
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Download again even if the input is already there
+    #[arg(long)]
+    force: bool,
+
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+#[derive(Args, Debug)]
+struct ExtractArgs {
+    /// Day the page belongs to, e.g. `7`
+    day: u8,
+
+    /// Saved HTML of the puzzle page
+    page: String,
+
+    /// Directory holding the per-day example directories
+    #[arg(long, default_value = EXAMPLES_DIR)]
+    dir: String,
+
+    /// Replace example files that already exist
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Args, Debug)]
+struct BenchArgs {
+    /// Day to benchmark, e.g. `7`
+    day: u8,
+
+    /// Benchmark only the given part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Warm-up time per part in milliseconds
+    #[arg(long, default_value_t = 500)]
+    warmup_ms: u64,
+
+    /// Measurement time per part in milliseconds
+    #[arg(long, default_value_t = 3000)]
+    measure_ms: u64,
+
+    /// Number of samples to collect per part
+    #[arg(long, default_value_t = 100)]
+    samples: u32,
+
+    /// Flag parts that got slower than the last recorded run, exits with failure if any did
+    #[arg(long)]
+    compare: bool,
+
+    /// Slowdown of the median in percent that `--compare` tolerates
+    #[arg(long, default_value_t = 10.0, requires = "compare")]
+    threshold: f64,
+
+    /// File every bench run is appended to
+    #[arg(long, default_value = HISTORY_PATH)]
+    history: String,
+}
+
+#[derive(Args, Debug)]
+struct RunArgs {
+    /// Day to run, e.g. `5`
+    #[arg(required_unless_present_any = ["all", "check"])]
+    day: Option<u8>,
+
+    /// Run only the given part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Run every implemented day
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+
+    /// Number of days to run at the same time, answers still print in day order
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: u16,
+
+    /// Give up on a part after this many milliseconds and report it as TIMEOUT
+    #[arg(long)]
+    timeout_ms: Option<u64>,
+
+    /// Read the puzzle input from this file instead of `input/DayNN.in`, `-` reads stdin
+    #[arg(short, long, conflicts_with = "all", requires = "day")]
+    input: Option<String>,
+
+    /// Print a table of parse and part timings
+    #[arg(short, long, conflicts_with = "format")]
+    time: bool,
+
+    /// Output format of answers
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Compare answers against the known answers file, runs every day unless one is given
+    #[arg(long, conflicts_with = "format")]
+    check: bool,
+
+    /// Known answers used by `--check`
+    #[arg(long, default_value = ANSWERS_PATH, requires = "check")]
+    answers: String,
+}
+
+/// Runs a day on its input, `line_copies` also works out what copying the input line by line
+/// would have allocated
+fn run(solution: &dyn DynSolution, part: Option<u8>, path: Option<&str>, timeout: Option<Duration>, line_copies: bool)
+       -> Result<Run, AocError> {
+    let path = path.map(|path| path.to_string())
+        .unwrap_or_else(|| input_path(solution.name()));
+    let start = Instant::now();
+    let input = read_input(&path)?;
+    let file = if path == "-" { "<stdin>" } else { &path };
+    let mut run = solution.run(&input, part, timeout).map_err(|err| err.in_file(file))?;
+    run.wall = start.elapsed();
+    if line_copies {
+        run.line_copy_allocs = alloc::line_copy(&input);
+    }
+    Ok(run)
+}
+
+/// Prints answers and errors of a run, returns whether everything succeeded
+fn print_text(name: &str, run: &Result<Run, AocError>) -> bool {
+    match run {
+        Ok(run) => {
+            let mut ok = true;
+            for (part, timed) in run.parts() {
+                match &timed.answer {
+                    Ok(answer) => println!("{} part {}: {}", name, part, answer),
+                    Err(err) => {
+                        eprintln!("{} part {}: {}", name, part, err);
+                        ok = false;
+                    }
+                }
+            }
+            ok
+        }
+        Err(err) => {
+            eprintln!("{}: {}", name, err);
+            false
+        }
+    }
+}
+
+/// Prints PASS/FAIL/MISSING for every part of a run, returns whether nothing failed
+fn print_check(name: &str, run: &Result<Run, AocError>, answers: &Answers, part: Option<u8>) -> bool {
+    let results = match run {
+        Ok(run) => run.parts()
+            .map(|(n, timed)| (n, timed.answer.as_deref()))
+            .collect::<Vec<_>>(),
+        Err(err) => [1, 2].into_iter()
+            .filter(|n| part.is_none() || part == Some(*n))
+            .map(|n| (n, Err(err)))
+            .collect(),
+    };
+    let mut ok = true;
+    for (n, actual) in results {
+        let verdict = Verdict::new(answers.get(name, n), actual);
+        if let Verdict::Fail { .. } | Verdict::Error(_) = verdict {
+            ok = false;
+        }
+        println!("{} part {}: {}", name, n, verdict);
+    }
+    ok
+}
+
+fn find(day: u8) -> Option<Box<dyn DynSolution>> {
+    let solution = days::find(day);
+    if solution.is_none() {
+        eprintln!("Day {} is not implemented, available days: {}", day,
+                  days::registry().iter()
+                      .map(|s| s.day().to_string())
+                      .collect::<Vec<_>>().join(", "));
+    }
+    solution
+}
+
+fn bench(args: BenchArgs) -> ExitCode {
+    let Some(solution) = find(args.day) else {
+        return ExitCode::FAILURE;
+    };
+    let history = match History::load(&args.history) {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let config = Config {
+        warmup: Duration::from_millis(args.warmup_ms),
+        measurement: Duration::from_millis(args.measure_ms),
+        samples: args.samples,
+    };
+    let commit = bench::git_commit();
+    let mut code = ExitCode::SUCCESS;
+    let mut records = Vec::new();
+    for part in [1, 2].into_iter().filter(|part| args.part.is_none() || args.part == Some(*part)) {
+        match bench::bench_day(solution.as_ref(), part, &config) {
+            Ok(stats) => {
+                println!("{} part {}: {}", solution.name(), part, stats);
+                let record = Record::new(&commit, solution.name(), part, &stats);
+                if args.compare {
+                    if let Some(last) = history.last(solution.name(), part) {
+                        let change = record.change(last) * 100.0;
+                        let short = &last.commit[..last.commit.len().min(12)];
+                        if change > args.threshold {
+                            println!("{} part {}: REGRESSION {:+.1}% vs {}", solution.name(), part, change, short);
+                            code = ExitCode::FAILURE;
+                        } else {
+                            println!("{} part {}: ok {:+.1}% vs {}", solution.name(), part, change, short);
+                        }
+                    } else {
+                        println!("{} part {}: no earlier run to compare against", solution.name(), part);
+                    }
+                }
+                records.push(record);
+            }
+            Err(err) => {
+                eprintln!("{} part {}: {}", solution.name(), part, err);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    if let Err(err) = History::append(&args.history, &records) {
+        eprintln!("Cannot record bench history in {}: {}", args.history, err);
+        code = ExitCode::FAILURE;
+    }
+    code
+}
+
+fn extract(args: ExtractArgs) -> ExitCode {
+    let Some(solution) = find(args.day) else {
+        return ExitCode::FAILURE;
+    };
+    let dir = Path::new(&args.dir).join(solution.name().to_lowercase());
+    let written = fs::read_to_string(&args.page)
+        .map_err(AocError::from)
+        .and_then(|html| extract::parse_page(&html))
+        .and_then(|examples| extract::write_examples(&dir, &examples, args.force));
+    match written {
+        Ok(files) => {
+            files.iter().for_each(|file| println!("Wrote {}", file.display()));
+            ExitCode::SUCCESS
+        }
+        Err(err @ AocError::Exists(_)) => {
+            eprintln!("{}, pass --force to replace it", err);
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("{}: {}", args.page, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(args: FetchArgs) -> ExitCode {
+    let path = input_path(&format!("Day{:02}", args.day));
+    let fetched = Client::configure(&args.site.config, args.site.base_url.as_deref())
+        .and_then(|client| client::fetch(&client, args.day, &path, args.force));
+    match fetched {
+        Ok(true) => println!("Downloaded {}", path),
+        Ok(false) => println!("{} is already downloaded, pass --force to download it again", path),
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn submit(args: SubmitArgs) -> ExitCode {
+    let Some(solution) = find(args.day) else {
+        return ExitCode::FAILURE;
+    };
+    let name = solution.name();
+    let answer = run(solution.as_ref(), Some(args.part), None, None, false)
+        .and_then(|run| run.parts().next().expect("requested part should run").1.answer.as_ref()
+            .map(String::clone)
+            .map_err(|err| AocError::NoSolution(err.to_string())));
+    let outcome = answer.and_then(|answer| {
+        Guesses::load(&args.guesses)?.check(name, args.part, &answer)?;
+        let client = Client::configure(&args.site.config, args.site.base_url.as_deref())?;
+        let outcome = client.submit(args.day, args.part, &answer)?;
+        Guesses::append(&args.guesses, &Guess::new(name, args.part, &answer, outcome))?;
+        Ok((answer, outcome))
+    });
+    match outcome {
+        Ok((answer, outcome)) => {
+            println!("{} part {}: {} is {}", name, args.part, answer, outcome);
+            if outcome == Outcome::Correct { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        }
+        Err(err) => {
+            eprintln!("{} part {}: {}", name, args.part, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn new_day(args: NewArgs) -> ExitCode {
+    match scaffold::scaffold(Path::new("."), args.day) {
+        Ok(files) => {
+            files.iter().for_each(|file| println!("Wrote {}", file.display()));
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn watch(args: WatchArgs) -> ExitCode {
+    let Some(solution) = find(args.day) else {
+        return ExitCode::FAILURE;
+    };
+    let options = Options {
+        interval: Duration::from_millis(args.interval_ms),
+        release: args.release,
+        timeout_ms: args.timeout_ms,
+    };
+    if let Err(err) = watch::watch(args.day, solution.name(), &options) {
+        eprintln!("{}", err);
+    }
+    ExitCode::FAILURE
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::Bench(args)) => bench(args),
+        Some(Command::Extract(args)) => extract(args),
+        Some(Command::Fetch(args)) => fetch(args),
+        Some(Command::Submit(args)) => submit(args),
+        Some(Command::New(args)) => new_day(args),
+        Some(Command::Watch(args)) => watch(args),
+        None => run_days(cli.run),
+    }
+}
+
+fn run_days(args: RunArgs) -> ExitCode {
+    let solutions = match args.day {
+        Some(day) => match find(day) {
+            Some(solution) => vec![solution],
+            None => return ExitCode::FAILURE,
+        },
+        None => days::registry(),
+    };
+    let answers = if args.check {
+        match Answers::load(&args.answers) {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprintln!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        Answers::default()
+    };
+    let mut code = ExitCode::SUCCESS;
+    let mut runs = Vec::new();
+    let timeout = args.timeout_ms.map(Duration::from_millis);
+    let run_day = |solution: &dyn DynSolution| run(solution, args.part, args.input.as_deref(), timeout, args.time);
+    run_ordered(&solutions, args.jobs.into(), |solution| run_day(solution.as_ref()), |solution, run| {
+        let run = run.and_then(|run| run);
+        let ok = match args.format {
+            _ if args.check => print_check(solution.name(), &run, &answers, args.part),
+            Format::Text => print_text(solution.name(), &run),
+            Format::Json => {
+                let report = Report::new(solution.day(), &run);
+                println!("{}", serde_json::to_string(&report).expect("report should serialise"));
+                report.error.is_none()
+            }
+        };
+        if !ok {
+            code = ExitCode::FAILURE;
+        }
+        if let Ok(run) = run {
+            runs.push((solution.name(), run));
+        }
+    });
+    if args.time {
+        let runs = runs.iter().map(|(name, run)| (*name, run)).collect::<Vec<_>>();
+        print!("\n{}", timing_table(&runs));
+        print!("{}", allocation_summary(&runs));
+    }
+    code
 }
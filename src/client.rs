@@ -0,0 +1,194 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use serde::Deserialize;
+use crate::err::AocError;
+
+pub const YEAR: u16 = 2023;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const CONFIG_PATH: &str = ".aoc.toml";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const USER_AGENT: &str = concat!("aoc-2023/", env!("CARGO_PKG_VERSION"),
+                                     " (+https://github.com/grzes5003/Advent-of-Code-2023)");
+
+/// Optional settings of the site client, every key can be overridden from the environment
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "https://adventofcode.com"
+/// ```
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl ClientConfig {
+    /// Loads the config, a missing file is an empty config
+    pub fn load(path: &str) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(s) => toml::from_str(&s).map_err(|err| AocError::Config(path.to_string(), err.to_string())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(ClientConfig::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Talks to the puzzle site, authenticated by the session cookie of a logged in browser
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Builds a client from the config file, `AOC_SESSION` and `AOC_BASE_URL` take precedence over
+    /// it and an explicit `base_url` over both
+    pub fn configure(config_path: &str, base_url: Option<&str>) -> Result<Self, AocError> {
+        let config = ClientConfig::load(config_path)?;
+        let session = env::var(SESSION_ENV).ok()
+            .or(config.session)
+            .filter(|session| !session.trim().is_empty())
+            .ok_or_else(|| AocError::Missing(format!("session token, set {} or `session` in {}",
+                                                     SESSION_ENV, config_path)))?;
+        let base_url = base_url.map(str::to_string)
+            .or(env::var(BASE_URL_ENV).ok())
+            .or(config.base_url)
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Ok(Client::new(&base_url, &session))
+    }
+
+    pub fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub(crate) fn post(&self, url: &str) -> ureq::Request {
+        self.agent.post(url).set("Cookie", &self.cookie())
+    }
+
+    /// Downloads the personal puzzle input of `day`
+    pub fn input(&self, day: u8) -> Result<String, AocError> {
+        let url = format!("{}/input", self.day_url(day));
+        self.agent.get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|err| http_error(&url, err))?
+            .into_string()
+            .map_err(AocError::from)
+    }
+}
+
+pub(crate) fn http_error(url: &str, err: ureq::Error) -> AocError {
+    match err {
+        ureq::Error::Status(code, response) => {
+            let status = response.status_text().to_string();
+            let body = response.into_string().unwrap_or_default();
+            AocError::Http(format!("{} returned {} {}: {}", url, code, status, body.trim()))
+        }
+        ureq::Error::Transport(err) => AocError::Http(format!("{}: {}", url, err)),
+    }
+}
+
+/// Downloads the input of `day` into `path` unless it is already there, returns whether it did
+pub fn fetch(client: &Client, day: u8, path: &str, force: bool) -> Result<bool, AocError> {
+    if !force && Path::new(path).exists() {
+        return Ok(false);
+    }
+    let input = client.input(day)?;
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, input)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread::{self, JoinHandle};
+    use super::*;
+
+    /// Answers the next request with `status` and `body`, the handle yields the raw request
+    pub fn serve(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                               status, body.len(), body);
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+            reader.into_inner().write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    pub fn temp_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-2023-{}-{}", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_fetch_caches() {
+        let (url, server) = serve("200 OK", "1 2 3\n");
+        let client = Client::new(&url, "secret\n");
+        let path = temp_path("Day17.in");
+        let path = path.to_str().unwrap();
+
+        assert!(fetch(&client, 17, path, false).unwrap());
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/17/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert!(request.contains(&format!("User-Agent: {}\r\n", USER_AGENT)));
+        assert_eq!(fs::read_to_string(path).unwrap(), "1 2 3\n");
+
+        // nothing is listening any more, a second request would fail
+        assert!(!fetch(&client, 17, path, false).unwrap());
+        assert!(fetch(&client, 17, path, true).is_err());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_error_status() {
+        let (url, server) = serve("400 Bad Request", "Puzzle inputs differ by user.");
+        let path = temp_path("Day18.in");
+        let err = fetch(&Client::new(&url, "expired"), 18, path.to_str().unwrap(), false).unwrap_err();
+        server.join().unwrap();
+        assert!(err.to_string().ends_with("returned 400 Bad Request: Puzzle inputs differ by user."));
+        assert!(!path.exists());
+    }
+}
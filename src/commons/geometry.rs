@@ -0,0 +1,298 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+use crate::err::AocError;
+
+/// Position on a plane, `y` grows downwards like the lines of an input
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> Point<T>
+    where T: Copy + Ord + Add<Output=T> + Sub<Output=T> {
+    /// Distance walking along the axes
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Distance walking diagonals too, the larger of the axis distances
+    pub fn chebyshev(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+fn abs_diff<T: Ord + Sub<Output=T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+impl Point<isize> {
+    pub fn step(self, dir: impl Into<Direction8>) -> Self {
+        self + dir.into().offset()
+    }
+
+    /// The point as a position of the `width` by `height` area, if it lies inside it
+    pub fn within(self, width: usize, height: usize) -> Option<Point<usize>> {
+        let (x, y) = (usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?);
+        (x < width && y < height).then_some(Point { x, y })
+    }
+}
+
+impl Point<usize> {
+    /// Neighbour in `dir`, `None` when it would leave the `width` by `height` area
+    pub fn step_within(self, dir: impl Into<Direction8>, width: usize, height: usize) -> Option<Self> {
+        Point::<isize>::from(self).step(dir).within(width, height)
+    }
+}
+
+impl From<Point<usize>> for Point<isize> {
+    fn from(value: Point<usize>) -> Self {
+        Point { x: value.x as isize, y: value.y as isize }
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(value: Point<T>) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl<T: Add<Output=T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point { x: self.x + rhs.x, y: self.y + rhs.y }
+    }
+}
+
+impl<T: Sub<Output=T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point { x: self.x - rhs.x, y: self.y - rhs.y }
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// One of the 4 axis directions, listed clockwise from up
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    fn rotate(self, quarters: usize) -> Self {
+        Direction::ALL[(self as usize + quarters) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(3)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    pub fn offset(self) -> Point<isize> {
+        Direction8::from(self).offset()
+    }
+
+    /// Reads `^`, `>`, `v` or `<`
+    pub fn from_arrow(ch: char) -> Option<Self> {
+        match ch {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Reads `U`, `R`, `D` or `L`, or the compass points `N`, `E`, `S` and `W`
+    pub fn from_letter(ch: char) -> Option<Self> {
+        match ch {
+            'U' | 'N' => Some(Direction::Up),
+            'R' | 'E' => Some(Direction::Right),
+            'D' | 'S' => Some(Direction::Down),
+            'L' | 'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+
+    pub fn letter(self) -> char {
+        match self {
+            Direction::Up => 'U',
+            Direction::Right => 'R',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+        }
+    }
+}
+
+/// Accepts both the arrow and the letter notation
+impl TryFrom<char> for Direction {
+    type Error = AocError;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        Direction::from_arrow(ch)
+            .or_else(|| Direction::from_letter(ch))
+            .ok_or_else(|| AocError::Invalid("direction", ch.to_string()))
+    }
+}
+
+impl FromStr for Direction {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Direction::try_from(ch),
+            _ => Err(AocError::Invalid("direction", s.to_string())),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+/// One of the 8 directions to a surrounding cell, listed clockwise from up
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up, Direction8::UpRight, Direction8::Right, Direction8::DownRight,
+        Direction8::Down, Direction8::DownLeft, Direction8::Left, Direction8::UpLeft,
+    ];
+
+    fn rotate(self, eighths: usize) -> Self {
+        Direction8::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// Turns by 45 degrees
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Turns by 45 degrees
+    pub fn turn_left(self) -> Self {
+        self.rotate(7)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn offset(self) -> Point<isize> {
+        let (x, y) = match self {
+            Direction8::Up => (0, -1),
+            Direction8::UpRight => (1, -1),
+            Direction8::Right => (1, 0),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (0, 1),
+            Direction8::DownLeft => (-1, 1),
+            Direction8::Left => (-1, 0),
+            Direction8::UpLeft => (-1, -1),
+        };
+        Point { x, y }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        Direction8::ALL[value as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownRight.reverse(), Direction8::UpLeft);
+        assert_eq!(Direction8::from(Direction::Left), Direction8::Left);
+    }
+
+    #[test]
+    fn test_notation() {
+        assert_eq!("v".parse::<Direction>().unwrap(), Direction::Down);
+        assert_eq!(Direction::try_from('L').unwrap(), Direction::Left);
+        assert_eq!(Direction::from_letter('N'), Some(Direction::Up));
+        assert!("UR".parse::<Direction>().is_err());
+        for dir in Direction::ALL {
+            assert_eq!(Direction::from_arrow(dir.arrow()), Some(dir));
+            assert_eq!(Direction::from_letter(dir.letter()), Some(dir));
+        }
+    }
+
+    #[test]
+    fn test_steps_and_distances() {
+        let corner = Point::new(0usize, 0);
+        assert_eq!(corner.step_within(Direction::Up, 3, 3), None);
+        assert_eq!(corner.step_within(Direction8::DownRight, 3, 3), Some(Point::new(1, 1)));
+        assert_eq!(Point::new(2usize, 2).step_within(Direction::Right, 3, 3), None);
+        assert_eq!(Point::new(0isize, 0).step(Direction::Left), Point::new(-1, 0));
+
+        let (a, b) = (Point::new(1isize, -2), Point::new(-3, 4));
+        assert_eq!(a.manhattan(&b), 10);
+        assert_eq!(a.chebyshev(&b), 6);
+        assert_eq!(Point::new(1usize, 5).manhattan(&Point::new(4, 2)), 6);
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use crate::commons::{Direction, Direction8, Point};
+use crate::err::AocError;
+
+/// Dense rectangular grid stored row by row, positions are `(x, y)` points with `y` growing
+/// downwards
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` columns over `cells` listed row by row
+    pub fn new(width: usize, cells: Vec<T>) -> Result<Self, AocError> {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        if width * height != cells.len() {
+            return Err(AocError::WrongFormat(format!("{} cells do not fill rows of {}", cells.len(), width)));
+        }
+        Ok(Grid { width, height, cells })
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+        where T: Clone {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /// Parses one row per line and one cell per char, errors are located at their line. Every
+    /// line has to be as long as the first one
+    pub fn parse<F>(s: &str, mut f: F) -> Result<Self, AocError>
+        where F: FnMut(char) -> Result<T, AocError> {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(s.len());
+        for (y, line) in s.lines().enumerate() {
+            let len = line.chars().count();
+            if *width.get_or_insert(len) != len {
+                return Err(AocError::WrongFormat(format!("row of {} cells in a grid {} wide", len, width.unwrap_or(0)))
+                    .at(y + 1, line));
+            }
+            for ch in line.chars() {
+                cells.push(f(ch).map_err(|err| err.at(y + 1, line))?);
+            }
+        }
+        Grid::new(width.unwrap_or(0), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Position at signed coordinates, `None` when it lies outside the grid
+    pub fn position(&self, point: Point<isize>) -> Option<Point<usize>> {
+        point.within(self.width, self.height)
+    }
+
+    /// Neighbour of `pos` in `dir`, `None` when it lies outside the grid
+    pub fn step(&self, pos: impl Into<Point<usize>>, dir: impl Into<Direction8>) -> Option<Point<usize>> {
+        pos.into().step_within(dir, self.width, self.height)
+    }
+
+    fn offset(&self, pos: Point<usize>) -> Option<usize> {
+        (pos.x < self.width).then_some(pos.y * self.width + pos.x)
+    }
+
+    pub fn get(&self, pos: impl Into<Point<usize>>) -> Option<&T> {
+        self.cells.get(self.offset(pos.into())?)
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Point<usize>>) -> Option<&mut T> {
+        let offset = self.offset(pos.into())?;
+        self.cells.get_mut(offset)
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item=Point<usize>> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item=(Point<usize>, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Cells above, right of, below and left of `pos` that lie inside the grid
+    pub fn neighbours4(&self, pos: impl Into<Point<usize>>) -> impl Iterator<Item=(Point<usize>, &T)> {
+        let pos = pos.into();
+        Direction::ALL.into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+            .map(|next| (next, &self[next]))
+    }
+
+    /// Cells surrounding `pos`, diagonals included, that lie inside the grid
+    pub fn neighbours8(&self, pos: impl Into<Point<usize>>) -> impl Iterator<Item=(Point<usize>, &T)> {
+        let pos = pos.into();
+        Direction8::ALL.into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+            .map(|next| (next, &self[next]))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&T> {
+        assert!(x < self.width, "column {} out of a grid {} wide", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+        where F: FnMut(&T) -> U {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+/// Unchecked indexing by a point or an `(x, y)` pair, panics outside the grid
+impl<T, P: Into<Point<usize>>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let pos = pos.into();
+        assert!(pos.x < self.width, "x {} out of a grid {} wide", pos.x, self.width);
+        &self.cells[pos.y * self.width + pos.x]
+    }
+}
+
+impl<T, P: Into<Point<usize>>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+        let pos = pos.into();
+        assert!(pos.x < self.width, "x {} out of a grid {} wide", pos.x, self.width);
+        &mut self.cells[pos.y * self.width + pos.x]
+    }
+}
+
+/// Renders every cell, one line per row
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456", |ch| ch.to_digit(10).ok_or(AocError::Invalid("digit", ch.to_string()))).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.position(Point::new(-1, 0)), None);
+        assert_eq!(grid.position(Point::new(2, 1)), Some(Point::new(2, 1)));
+        assert_eq!(grid.step((2, 1), Direction::Right), None);
+        assert_eq!(grid.step((2, 1), Direction8::UpLeft), Some(Point::new(1, 0)));
+        assert_eq!(grid.to_string(), "123\n456\n");
+
+        match Grid::parse("12\n3x", |ch| ch.to_digit(10).ok_or(AocError::Invalid("digit", ch.to_string()))) {
+            Err(AocError::Parse(err)) => assert_eq!((err.line, err.columns), (2, 2..3)),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(Grid::parse("12\n3", Ok).is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits();
+        let values = |cells: Vec<(_, &u32)>| cells.into_iter().map(|(_, v)| *v).collect::<Vec<_>>();
+        assert_eq!(values(grid.neighbours4((0, 0)).collect()), vec![2, 4]);
+        assert_eq!(values(grid.neighbours4((1, 1)).collect()), vec![2, 6, 4]);
+        assert_eq!(values(grid.neighbours8((1, 0)).collect()), vec![3, 6, 5, 4, 1]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut grid = digits();
+        grid[(0, 1)] = 0;
+        assert_eq!(grid.row(1), &[0, 5, 6]);
+        assert_eq!(grid.rows().map(|row| row.iter().sum::<u32>()).collect::<Vec<_>>(), vec![6, 11]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.columns().map(|column| column.sum::<u32>()).collect::<Vec<_>>(), vec![1, 7, 9]);
+        assert_eq!(grid.map(|v| v % 2).iter().filter(|(_, v)| **v == 1).map(|(pos, _)| pos.into()).collect::<Vec<(usize, usize)>>(),
+                   vec![(0, 0), (2, 0), (1, 1)]);
+    }
+}
@@ -0,0 +1,192 @@
+use std::ops::{Add, Range, Sub};
+
+/// Set of values stored as sorted half-open ranges that neither overlap nor touch
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+impl<T> IntervalSet<T>
+    where T: Copy + Ord + Default + Add<Output=T> + Sub<Output=T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of values in the set
+    pub fn len(&self) -> T {
+        self.ranges.iter().fold(T::default(), |len, range| len + (range.end - range.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges.get(idx).is_some_and(|range| range.start <= value)
+    }
+
+    /// Adds every value of `range`, merging it with the ranges it overlaps or touches
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|other| other.end < range.start);
+        let last = self.ranges.partition_point(|other| other.start <= range.end);
+        let merged = if first < last {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        other.ranges.iter().for_each(|range| union.insert(range.clone()));
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut ours, mut theirs) = (self.ranges.iter().peekable(), other.ranges.iter().peekable());
+        let mut intersection = IntervalSet::new();
+        while let (Some(a), Some(b)) = (ours.peek(), theirs.peek()) {
+            intersection.insert(a.start.max(b.start)..a.end.min(b.end));
+            if a.end < b.end {
+                ours.next();
+            } else {
+                theirs.next();
+            }
+        }
+        intersection
+    }
+
+    /// Values of this set that are not in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = IntervalSet::new();
+        for range in self.ranges.iter() {
+            let mut start = range.start;
+            let first = other.ranges.partition_point(|cut| cut.end <= range.start);
+            for cut in other.ranges[first..].iter().take_while(|cut| cut.start < range.end) {
+                difference.insert(start..cut.start.min(range.end));
+                start = start.max(cut.end);
+            }
+            difference.insert(start..range.end);
+        }
+        difference
+    }
+
+    /// Splits into the values below `at` and the rest
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let (mut below, mut rest) = (IntervalSet::new(), IntervalSet::new());
+        for range in self.ranges.iter() {
+            below.insert(range.start..range.end.min(at));
+            rest.insert(range.start.max(at)..range.end);
+        }
+        (below, rest)
+    }
+
+    /// Moves every value by the distance from `from` to `to`, which works for unsigned values
+    /// moving down too
+    pub fn shift(&self, from: T, to: T) -> Self {
+        let moved = |value: T| if to >= from { value + (to - from) } else { value - (from - to) };
+        IntervalSet { ranges: self.ranges.iter().map(|range| moved(range.start)..moved(range.end)).collect() }
+    }
+}
+
+impl<T> FromIterator<Range<T>> for IntervalSet<T>
+    where T: Copy + Ord + Default + Add<Output=T> + Sub<Output=T> {
+    fn from_iter<I: IntoIterator<Item=Range<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        iter.into_iter().for_each(|range| set.insert(range));
+        set
+    }
+}
+
+impl<T> From<Range<T>> for IntervalSet<T>
+    where T: Copy + Ord + Default + Add<Output=T> + Sub<Output=T> {
+    fn from(range: Range<T>) -> Self {
+        IntervalSet::from_iter([range])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use proptest::prelude::*;
+    use super::*;
+
+    fn model(set: &IntervalSet<u32>) -> HashSet<u32> {
+        set.ranges().iter().flat_map(|range| range.clone()).collect()
+    }
+
+    fn ranges() -> impl Strategy<Value=Vec<Range<u32>>> {
+        prop::collection::vec((0u32..64, 0u32..12).prop_map(|(start, len)| start..start + len), 0..8)
+    }
+
+    fn assert_normalised(set: &IntervalSet<u32>) {
+        assert!(set.ranges().iter().all(|range| !range.is_empty()), "{:?}", set);
+        assert!(set.ranges().windows(2).all(|pair| pair[0].end < pair[1].start), "{:?}", set);
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set = IntervalSet::from_iter([5..8, 1..3, 10..12]);
+        assert_eq!(set.ranges(), &[1..3, 5..8, 10..12]);
+        set.insert(3..5);
+        assert_eq!(set.ranges(), &[1..8, 10..12]);
+        set.insert(0..20);
+        assert_eq!(set.ranges(), &[0..20]);
+        assert_eq!(set.len(), 20);
+        assert_eq!(set.shift(1, 4).ranges(), &[3..23]);
+        assert_eq!(IntervalSet::from(5u64..8).shift(5, 2).ranges(), &[2..5]);
+        assert!(IntervalSet::from(3..3).is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_hash_set(a in ranges(), b in ranges(), at in 0u32..80, value in 0u32..80) {
+            let (set_a, set_b) = (IntervalSet::from_iter(a.clone()), IntervalSet::from_iter(b.clone()));
+            let (model_a, model_b): (HashSet<u32>, HashSet<u32>) =
+                (a.into_iter().flatten().collect(), b.into_iter().flatten().collect());
+            let ops = [
+                (set_a.union(&set_b), &model_a | &model_b),
+                (set_a.intersection(&set_b), &model_a & &model_b),
+                (set_a.difference(&set_b), &model_a - &model_b),
+            ];
+            for (set, expected) in ops.iter() {
+                assert_normalised(set);
+                prop_assert_eq!(&model(set), expected);
+                prop_assert_eq!(set.len() as usize, expected.len());
+            }
+            prop_assert_eq!(model(&set_a), model_a.clone());
+            prop_assert_eq!(set_a.contains(value), model_a.contains(&value));
+            prop_assert_eq!(set_a.min(), model_a.iter().min().copied());
+
+            let (below, rest) = set_a.split_at(at);
+            assert_normalised(&below);
+            assert_normalised(&rest);
+            prop_assert_eq!(model(&below), model_a.iter().copied().filter(|v| *v < at).collect::<HashSet<_>>());
+            prop_assert_eq!(model(&rest), model_a.iter().copied().filter(|v| *v >= at).collect::<HashSet<_>>());
+
+            let shifted = set_a.shift(at, 100);
+            assert_normalised(&shifted);
+            prop_assert_eq!(model(&shifted), model_a.iter().map(|v| v + 100 - at).collect::<HashSet<_>>());
+            prop_assert_eq!(shifted.shift(100, at), set_a);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+mod geometry;
+mod grid;
+mod interval;
+pub mod number;
+pub mod search;
+
+pub use geometry::{Direction, Direction8, Point};
+pub use grid::Grid;
+pub use interval::IntervalSet;
+pub use number::{gcd, lcm};
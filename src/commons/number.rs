@@ -0,0 +1,234 @@
+use std::ops::{Div, Rem};
+
+/// Primitive integers the gcd and lcm helpers work over
+pub trait Integer: Copy + Ord + Rem<Output=Self> + Div<Output=Self> {
+    const ZERO: Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn checked_abs(self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    (signed: $($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                <$t>::checked_abs(self)
+            }
+        }
+    )*};
+    (unsigned: $($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                Some(self)
+            }
+        }
+    )*};
+}
+
+integer!(signed: i8, i16, i32, i64, i128, isize);
+integer!(unsigned: u8, u16, u32, u64, u128, usize);
+
+/// Greatest common divisor, never negative unless it is `T::MIN` itself. `gcd(0, 0)` is 0
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a.checked_abs().unwrap_or(a)
+}
+
+/// Greatest common divisor of all `values`, `None` when there are none
+pub fn gcd_all<T, I>(values: I) -> Option<T>
+    where T: Integer, I: IntoIterator<Item=T> {
+    values.into_iter().reduce(gcd)
+}
+
+/// Least common multiple of all `values`, `None` when there are none or it overflows `T`
+pub fn lcm<T, I>(values: I) -> Option<T>
+    where T: Integer, I: IntoIterator<Item=T> {
+    let mut values = values.into_iter();
+    let first = values.next()?.checked_abs()?;
+    values.try_fold(first, |lcm, value| {
+        let g = gcd(lcm, value);
+        if g == T::ZERO {
+            return Some(T::ZERO);
+        }
+        (lcm / g).checked_mul(value)?.checked_abs()
+    })
+}
+
+/// Extended Euclid, `(g, x, y)` with `a * x + b * y == g` and `g` the non-negative gcd
+pub fn extended_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_i128(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+/// `a * b mod m` for `a` and `b` in `0..m`, falls back to doubling when the product overflows
+fn mul_mod(mut a: i128, mut b: i128, m: i128) -> i128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let add = |a: i128, b: i128| if a >= m - b { a - (m - b) } else { a + b };
+    let mut product = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add(product, a);
+        }
+        a = add(a, a);
+        b >>= 1;
+    }
+    product
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, `None` when `a` and `m` are not coprime or `m` is
+/// not positive
+pub fn mod_inverse_i128(a: i128, m: i128) -> Option<i128> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd_i128(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    mod_inverse_i128(a as i128, m as i128).map(|x| x as i64)
+}
+
+/// `base ^ exp mod m` in `0..m`, panics when `m` is not positive
+pub fn mod_pow_i128(base: i128, mut exp: u128, m: i128) -> i128 {
+    assert!(m > 0, "modulus {} is not positive", m);
+    let mut base = base.rem_euclid(m);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+pub fn mod_pow(base: i64, exp: u64, m: i64) -> i64 {
+    mod_pow_i128(base as i128, exp as u128, m as i128) as i64
+}
+
+/// Chinese Remainder Theorem over `(remainder, modulus)` pairs whose moduli need not be coprime.
+/// Gives `(r, m)` such that `x ≡ r (mod m)` are exactly the solutions, with `r` in `0..m` and `m`
+/// the lcm of the moduli. `None` when the congruences contradict each other, a modulus is not
+/// positive or `m` overflows
+pub fn crt_i128<I>(congruences: I) -> Option<(i128, i128)>
+    where I: IntoIterator<Item=(i128, i128)> {
+    congruences.into_iter().try_fold((0, 1), |(r, m), (a, n)| {
+        if n <= 0 {
+            return None;
+        }
+        let a = a.rem_euclid(n);
+        let (g, p, _) = extended_gcd_i128(m, n);
+        if (a - r) % g != 0 {
+            return None;
+        }
+        // r + m * k solves both for k ≡ (a - r) / g * p (mod n / g)
+        let step = n / g;
+        let k = mul_mod(((a - r) / g).rem_euclid(step), p.rem_euclid(step), step);
+        let lcm = (m / g).checked_mul(n)?;
+        Some((r + m * k, lcm))
+    })
+}
+
+/// `crt_i128` for moduli whose lcm fits an `i64`
+pub fn crt<I>(congruences: I) -> Option<(i64, i64)>
+    where I: IntoIterator<Item=(i64, i64)> {
+    let (r, m) = crt_i128(congruences.into_iter().map(|(a, n)| (a as i128, n as i128)))?;
+    Some((r.try_into().ok()?, m.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(gcd_all([24, 60, 36]), Some(12));
+        assert_eq!(gcd_all(Vec::<u64>::new()), None);
+        assert_eq!(lcm([4usize, 6, 10]), Some(60));
+        assert_eq!(lcm([-4i64, 6]), Some(12));
+        assert_eq!(lcm([0u32, 5]), Some(0));
+        assert_eq!(lcm(Vec::<usize>::new()), None);
+        assert_eq!(lcm([1u8 << 7, 3]), None);
+        assert_eq!(lcm([u64::MAX, u64::MAX]), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_modular() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+        // i128::MAX is the Mersenne prime 2^127 - 1, so Fermat holds without the products fitting
+        assert_eq!(mod_pow_i128(3, i128::MAX as u128 - 1, i128::MAX), 1);
+        assert_eq!(mod_inverse_i128(i128::MAX - 1, i128::MAX), Some(i128::MAX - 1));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        let primes = [(1, 1_000_000_007), (2, 998_244_353), (3, 1_000_000_009)];
+        assert_eq!(crt(primes), None);
+        let (r, m) = crt_i128(primes.map(|(a, n)| (a as i128, n as i128))).unwrap();
+        assert_eq!(m, 1_000_000_007 * 998_244_353 * 1_000_000_009);
+        assert!(primes.iter().all(|&(a, n)| r % n as i128 == a as i128));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_crt_matches_brute_force(congruences in prop::collection::vec((0i64..40, 1i64..13), 1..4)) {
+            let period = lcm(congruences.iter().map(|&(_, n)| n)).unwrap();
+            let solves = |x: i64| congruences.iter().all(|&(a, n)| x % n == a % n);
+            let expected = (0..period).find(|&x| solves(x)).map(|x| (x, period));
+            prop_assert_eq!(crt(congruences.iter().copied()), expected);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search from a single start state found
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    start: N,
+    goal: Option<N>,
+    distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    visited: usize,
+}
+
+impl<N, C> Search<N, C>
+    where N: Eq + Hash + Clone, C: Copy {
+    fn new(start: N, zero: C) -> Self {
+        Search {
+            distances: HashMap::from([(start.clone(), zero)]),
+            start,
+            goal: None,
+            parents: HashMap::new(),
+            visited: 0,
+        }
+    }
+
+    /// First state that satisfied the goal, `None` when the search ran out of states
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// Cost of reaching the goal
+    pub fn cost(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    pub fn distance(&self, state: &N) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    /// Cost of the best path found to every reached state. Without a goal these are the shortest
+    /// distances to every reachable state
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    /// Number of states taken off the frontier and expanded
+    pub fn visited(&self) -> usize {
+        self.visited
+    }
+
+    /// States from the start up to and including `state`
+    pub fn path_to(&self, state: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        debug_assert!(path[0] == self.start);
+        Some(path)
+    }
+
+    /// States from the start up to and including the goal
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+}
+
+/// Breadth-first search counting steps, stops at the first state satisfying `goal`. A goal
+/// that never holds explores everything reachable
+pub fn bfs<N, FN, IN, FG>(start: N, mut successors: FN, mut goal: FG) -> Search<N, usize>
+    where N: Eq + Hash + Clone,
+          FN: FnMut(&N) -> IN,
+          IN: IntoIterator<Item=N>,
+          FG: FnMut(&N) -> bool {
+    let mut search = Search::new(start.clone(), 0);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, steps)) = queue.pop_front() {
+        search.visited += 1;
+        if goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        for next in successors(&state) {
+            if !search.distances.contains_key(&next) {
+                search.distances.insert(next.clone(), steps + 1);
+                search.parents.insert(next.clone(), state.clone());
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Frontier entry ordered by its priority alone, lowest first
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    state: N,
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Cheapest paths over non-negative step costs, `successors` yields every next state with the
+/// cost of stepping to it
+pub fn dijkstra<N, C, FN, IN, FG>(start: N, successors: FN, goal: FG) -> Search<N, C>
+    where N: Eq + Hash + Clone,
+          C: Copy + Ord + Default + Add<Output=C>,
+          FN: FnMut(&N) -> IN,
+          IN: IntoIterator<Item=(N, C)>,
+          FG: FnMut(&N) -> bool {
+    astar(start, successors, |_| C::default(), goal)
+}
+
+/// Dijkstra guided by `heuristic`, which must never overestimate the remaining cost to a goal
+pub fn astar<N, C, FN, IN, FH, FG>(start: N, mut successors: FN, mut heuristic: FH, mut goal: FG) -> Search<N, C>
+    where N: Eq + Hash + Clone,
+          C: Copy + Ord + Default + Add<Output=C>,
+          FN: FnMut(&N) -> IN,
+          IN: IntoIterator<Item=(N, C)>,
+          FH: FnMut(&N) -> C,
+          FG: FnMut(&N) -> bool {
+    let mut search = Search::new(start.clone(), C::default());
+    let mut frontier = BinaryHeap::from([Entry { priority: heuristic(&start), cost: C::default(), state: start }]);
+    while let Some(Entry { cost, state, .. }) = frontier.pop() {
+        if search.distance(&state).is_some_and(|best| best < cost) {
+            continue;
+        }
+        search.visited += 1;
+        if goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        for (next, step) in successors(&state) {
+            let cost = cost + step;
+            if search.distance(&next).is_none_or(|best| cost < best) {
+                search.distances.insert(next.clone(), cost);
+                search.parents.insert(next.clone(), state.clone());
+                frontier.push(Entry { priority: cost + heuristic(&next), cost, state: next });
+            }
+        }
+    }
+    search
+}
+
+/// Where iterating a function starts repeating itself
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps before the first state that repeats
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    /// Number of steps after which the state is the same as after `steps` steps
+    pub fn reduce(&self, steps: usize) -> usize {
+        if steps < self.start {
+            steps
+        } else {
+            self.start + (steps - self.start) % self.len
+        }
+    }
+}
+
+/// Finds the cycle of `f` iterated from `start` with Brent's algorithm, `f` has to be
+/// deterministic and its states finite
+pub fn find_cycle<N, F>(start: N, mut f: F) -> Cycle
+    where N: Eq + Clone, F: FnMut(&N) -> N {
+    let (mut power, mut len) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = f(&hare);
+        len += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..len {
+        hare = f(&hare);
+    }
+    let mut first = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        first += 1;
+    }
+    Cycle { start: first, len }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commons::{Direction, Grid, Point};
+    use super::*;
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.";
+
+    fn open(grid: &Grid<bool>, pos: &Point<usize>) -> Vec<Point<usize>> {
+        grid.neighbours4(*pos).filter(|(_, wall)| !**wall).map(|(next, _)| next).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = Grid::parse(MAZE, |ch| Ok(ch == '#')).unwrap();
+        let goal = Point::new(6, 0);
+        let search = bfs(Point::new(0, 0), |pos| open(&grid, pos), |pos| *pos == goal);
+        assert_eq!(search.cost(), Some(12));
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), (Point::new(0, 0), goal));
+        assert!(path.windows(2).all(|step| step[0].manhattan(&step[1]) == 1));
+
+        let everything = bfs(Point::new(0, 0), |pos| open(&grid, pos), |_| false);
+        assert_eq!(everything.goal(), None);
+        assert_eq!(everything.distances().len(), 20);
+        assert_eq!(everything.visited(), 20);
+        assert_eq!(everything.distance(&Point::new(6, 3)), Some(11));
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        // stepping up or down costs 10
+        let grid = Grid::parse(MAZE, |ch| Ok(ch == '#')).unwrap();
+        let weighted = |pos: &Point<usize>| Direction::ALL.into_iter()
+            .filter_map(|dir| Some((dir, grid.step(*pos, dir)?)))
+            .filter(|(_, next)| !grid[*next])
+            .map(|(dir, next)| (next, if dir.is_vertical() { 10 } else { 1 }))
+            .collect::<Vec<_>>();
+        let goal = Point::new(6, 3);
+        let dijkstra = dijkstra(Point::new(0, 0), weighted, |pos| *pos == goal);
+        let astar = astar(Point::new(0, 0), weighted, |pos| pos.manhattan(&goal), |pos| *pos == goal);
+        assert_eq!(dijkstra.cost(), Some(56));
+        assert_eq!(astar.cost(), Some(56));
+        assert_eq!(astar.path().unwrap().last(), Some(&goal));
+        assert!(astar.visited() <= dijkstra.visited());
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 3 -> 10 -> 5 -> 16 -> 8 -> 4 -> 2 -> 1 -> 4
+        let collatz = |n: &u64| if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        let cycle = find_cycle(3, collatz);
+        assert_eq!(cycle, Cycle { start: 5, len: 3 });
+        assert_eq!(cycle.reduce(1_000_000_000), 7);
+        assert_eq!(cycle.reduce(4), 4);
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use lazy_static::lazy_static;
+use crate::err::AocError;
 use crate::util::Solution;
 
 
@@ -58,11 +59,12 @@ impl Rev for String {
 }
 
 impl<'a> Solution<'a> for Day {
-    type Input = Vec<String>;
-    type Output = Option<Num>;
-    const DAY: &'a str = "Day01";
+    type Input = Vec<&'a str>;
+    type Output1 = Num;
+    type Output2 = Num;
+    const DAY: &'static str = "Day01";
 
-    fn part1(input: &Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
         input.into_iter()
             .map(|line|
                 (line.chars().find(|ch| ch.is_digit(10)),
@@ -71,13 +73,14 @@ impl<'a> Solution<'a> for Day {
                 (t1.and_then(|i| i.to_digit(10)),
                  t2.and_then(|i| i.to_digit(10))))
             .fold(Some(0u32), |acc, (a, b)| Some(a? * 10 + b? + acc?))
+            .ok_or(AocError::NoSolution("line without digits".to_string()))
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
         input.into_iter()
             .map(|line|
-                (Day::despell(line.to_owned()),
-                 Day::rdespell(line.to_owned())))
+                (Day::despell(line.to_string()),
+                 Day::rdespell(line.to_string())))
             .map(|(a, b)|
                 (a.chars().find(|ch| ch.is_digit(10)),
                  b.chars().rfind(|ch| ch.is_digit(10))))
@@ -85,46 +88,18 @@ impl<'a> Solution<'a> for Day {
                 (t1.and_then(|i| i.to_digit(10)),
                  t2.and_then(|i| i.to_digit(10))))
             .fold(Some(0u32), |acc, (a, b)| Some(a? * 10 + b? + acc?))
+            .ok_or(AocError::NoSolution("line without digits".to_string()))
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        raw_input.to_owned()
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        Ok(raw_input.lines().collect())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
     use crate::bench_day;
-    use crate::util::parse;
-
-    #[test]
-    fn test_example_t1() {
-        let input = vec![
-            "1abc2".to_string(),
-            "pqr3stu8vwx".to_string(),
-            "a1b2c3d4e5f".to_string(),
-            "treb7uchet".to_string(),
-        ];
-        let input = Day::parse_input(&input);
-        assert_eq!(Day::part1(&input), Some(142));
-    }
-
-    #[test]
-    fn test_example_t2() {
-        let input = vec![
-            "two1nine".to_string(),
-            "eightwothree".to_string(),
-            "abcone2threexyz".to_string(),
-            "xtwone3four".to_string(),
-            "4nineeightseven2".to_string(),
-            "zoneight234".to_string(),
-            "7pqrstsixteen".to_string(),
-        ];
-        let input = Day::parse_input(&input);
-        assert_eq!(Day::part2(&input), Some(281));
-    }
 
     bench_day!(Day);
 }
\ No newline at end of file
@@ -1,24 +1,15 @@
-use std::num::ParseIntError;
 use std::str::FromStr;
 use itertools::Itertools;
-use thiserror::Error;
-use crate::util::Solution;
+use crate::err::{number, AocError};
+use crate::util::{parse_lines, Solution};
 
 
-#[derive(Error, Debug)]
-pub enum ParseError {
-    #[error("Cannot parse to int: {0}")]
-    PIE(#[from] ParseIntError),
-    #[error("Unexpected token: {0}")]
-    UnexpectedToken(String),
-}
-
 // R G B
 #[derive(Debug)]
 struct Bag(u32, u32, u32);
 
 impl FromStr for Bag {
-    type Err = ParseError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split(",");
@@ -26,10 +17,10 @@ impl FromStr for Bag {
         for part in parts.into_iter() {
             let slices = part.trim().split(' ').collect::<Vec<_>>();
             match slices.as_slice() {
-                [a, "red"] => { r = a.parse::<u32>()?; }
-                [a, "green"] => { g = a.parse::<u32>()?; }
-                [a, "blue"] => { b = a.parse::<u32>()?; }
-                _ => Err(ParseError::UnexpectedToken(part.to_owned()))?
+                [a, "red"] => { r = number(a)?; }
+                [a, "green"] => { g = number(a)?; }
+                [a, "blue"] => { b = number(a)?; }
+                _ => Err(AocError::Invalid("cubes", part.trim().to_owned()))?
             }
         }
         Ok(Bag(r, g, b))
@@ -43,14 +34,16 @@ pub struct Game {
 }
 
 impl FromStr for Game {
-    type Err = ParseError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split(":");
-        let uid = parts.next().unwrap()
-            .split(' ').skip(1).next().unwrap()
-            .parse::<u32>().unwrap();
-        let mut raw_rounds = parts.next().unwrap().split(";");
+        let uid = number(parts.next()
+            .and_then(|game| game.split(' ').nth(1))
+            .ok_or(AocError::WrongFormat(s.to_owned()))?)?;
+        let raw_rounds = parts.next()
+            .ok_or(AocError::WrongFormat(s.to_owned()))?
+            .split(";");
         let rounds = raw_rounds.into_iter()
             .map(|part| Bag::from_str(part))
             .collect::<Result<_, _>>()?;
@@ -68,11 +61,12 @@ impl Day {
 
 impl<'a> Solution<'a> for Day {
     type Input = Vec<Game>;
-    type Output = u32;
-    const DAY: &'a str = "Day02";
+    type Output1 = u32;
+    type Output2 = u32;
+    const DAY: &'static str = "Day02";
 
-    fn part1(input: &Self::Input) -> Self::Output {
-        input.into_iter()
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
+        Ok(input.iter()
             .filter(|game| {
                 game.rounds.iter()
                     .all(|bag| bag.0 <= Self::limit().0
@@ -80,14 +74,13 @@ impl<'a> Solution<'a> for Day {
                         && bag.2 <= Self::limit().2)
             })
             .map(|game| game.uid)
-            .sum()
+            .sum())
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
-        input.into_iter()
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
+        Ok(input.iter()
             .map(|game| {
                 let (mut r, mut g, mut b) = (0, 0, 0);
-                println!("{:?}", game);
                 for round in game.rounds.iter() {
                     r = r.max(round.0);
                     g = g.max(round.1);
@@ -95,13 +88,11 @@ impl<'a> Solution<'a> for Day {
                 };
                 r * g * b
             })
-            .sum()
+            .sum())
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        raw_input.iter()
-            .map(|line| Game::from_str(line.as_str()).unwrap())
-            .collect()
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        parse_lines(raw_input, Game::from_str)
     }
 }
 
@@ -111,15 +102,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_example_t2() {
-        let input =
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".lines()
-                .map(|line| Game::from_str(line).unwrap())
-                .collect::<Vec<_>>();
-        assert_eq!(Day::part2(&input), 2286);
+    fn test_parse_error_location() {
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 blue; 2 purple, 1 red";
+        match Day::parse_input(input) {
+            Err(AocError::Parse(err)) => {
+                assert_eq!(err.line, 2);
+                assert_eq!(err.columns, 17..25);
+            }
+            _ => panic!("expected a located parse error"),
+        }
     }
 }
\ No newline at end of file
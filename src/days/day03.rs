@@ -1,189 +1,80 @@
-use std::collections::HashSet;
-use std::fmt::Debug;
+use std::collections::HashMap;
+use crate::commons::{Grid, Point};
+use crate::err::{number, AocError};
 use crate::util::Solution;
 
-trait Item: Debug {
-    fn adjacent(&self, other: &impl Item) -> bool {
-        self.borders().intersection(&other.occupy()).count() > 0
-    }
-    fn borders(&self) -> HashSet<Pos>;
-    fn occupy(&self) -> HashSet<Pos>;
-}
-
-type XY = i32;
-
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
-struct Pos(XY, XY);
-
-#[derive(Debug)]
-struct Num {
-    repl: String,
-    pos: Pos,
-}
-
-impl Num {
-    fn val(&self) -> u32 {
-        self.repl.parse::<u32>().unwrap()
-    }
-}
-
-impl Item for Num {
-    fn borders(&self) -> HashSet<Pos> {
-        let mut borders = HashSet::new();
-        for i in -1..=self.repl.len() as XY {
-            borders.insert(Pos(self.pos.0 + i, self.pos.1 + 1));
-            borders.insert(Pos(self.pos.0 + i, self.pos.1 - 1));
-        }
-        borders.insert(Pos(self.pos.0 - 1, self.pos.1));
-        borders.insert(Pos(self.pos.0 + self.repl.len() as XY, self.pos.1));
-        borders
-    }
-
-    fn occupy(&self) -> HashSet<Pos> {
-        let mut borders = HashSet::new();
-        for i in 0..self.repl.len() {
-            borders.insert(Pos(self.pos.0 + i as XY, self.pos.1));
-        }
-        borders
-    }
-}
-
 #[derive(Debug)]
-struct Symbol {
-    ch: char,
-    pos: Pos,
+struct Num<'a> {
+    repl: &'a str,
+    val: u32,
+    pos: Point<usize>,
 }
 
-impl Item for Symbol {
-    fn borders(&self) -> HashSet<Pos> {
-        vec![
-            Pos(self.pos.0 - 1, self.pos.1),
-            Pos(self.pos.0 + 1, self.pos.1),
-            Pos(self.pos.0, self.pos.1 - 1),
-            Pos(self.pos.0, self.pos.1 + 1),
-            Pos(self.pos.0 + 1, self.pos.1 + 1),
-            Pos(self.pos.0 - 1, self.pos.1 + 1),
-            Pos(self.pos.0 + 1, self.pos.1 - 1),
-            Pos(self.pos.0 - 1, self.pos.1 - 1),
-        ].into_iter().collect()
-    }
-
-    fn occupy(&self) -> HashSet<Pos> {
-        vec![
-            Pos(self.pos.0, self.pos.1),
-        ].into_iter().collect()
+impl Num<'_> {
+    /// Symbols touching any digit of the number, each listed once
+    fn symbols(&self, grid: &Grid<char>) -> Vec<(Point<usize>, char)> {
+        let Point { x, y } = self.pos;
+        let mut symbols = (x..x + self.repl.len())
+            .flat_map(|x| grid.neighbours8((x, y)))
+            .filter(|(_, ch)| !ch.is_ascii_digit() && **ch != '.')
+            .map(|(pos, ch)| (pos, *ch))
+            .collect::<Vec<_>>();
+        symbols.sort();
+        symbols.dedup();
+        symbols
     }
 }
 
 #[derive(Debug)]
-pub struct Schematic {
-    nums: Vec<Num>,
-    symbols: Vec<Symbol>,
+pub struct Schematic<'a> {
+    grid: Grid<char>,
+    nums: Vec<Num<'a>>,
 }
 
 pub struct Day;
 
 impl<'a> Solution<'a> for Day {
-    type Input = Schematic;
-    type Output = u32;
-    const DAY: &'a str = "Day03";
-
-    fn part1(input: &Self::Input) -> Self::Output {
-        input.nums.iter()
-            .filter(|num| {
-                input.symbols.iter()
-                    .any(|symbol| num.adjacent(symbol))
-            }).map(|num| num.val()).sum()
+    type Input = Schematic<'a>;
+    type Output1 = u32;
+    type Output2 = u32;
+    const DAY: &'static str = "Day03";
+
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
+        Ok(input.nums.iter()
+            .filter(|num| !num.symbols(&input.grid).is_empty())
+            .map(|num| num.val).sum())
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
-        input.symbols.iter()
-            .filter(|symbol| symbol.ch == '*')
-            .map(|symbol| {
-                input.nums.iter()
-                    .filter(|num| num.adjacent(symbol))
-                    .map(|num| num.val()).collect::<Vec<_>>()
-            }).filter(|nums| nums.len() == 2)
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
+        let mut gears: HashMap<Point<usize>, Vec<u32>> = HashMap::new();
+        for num in input.nums.iter() {
+            for (pos, _) in num.symbols(&input.grid).into_iter().filter(|(_, ch)| *ch == '*') {
+                gears.entry(pos).or_default().push(num.val);
+            }
+        }
+        Ok(gears.values()
+            .filter(|nums| nums.len() == 2)
             .map(|nums| nums[0] * nums[1])
-            .sum()
+            .sum())
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
         let mut nums = Vec::new();
-        let mut symbols = Vec::new();
-
-        raw_input.into_iter().enumerate()
-            .for_each(|(y, line)| {
-                let mut num_buffer = String::new();
-                line.chars().enumerate()
-                    .for_each(|(x, ch)| {
-                        match ch {
-                            ch if ch.is_digit(10) => num_buffer.push(ch),
-                            other => {
-                                if !num_buffer.is_empty() {
-                                    nums.push(Num {
-                                        repl: num_buffer.clone(),
-                                        pos: Pos((x - num_buffer.len()) as XY, y as XY),
-                                    });
-                                    num_buffer.clear();
-                                }
-                                match other {
-                                    '.' => (),
-                                    symbol => symbols.push(Symbol {
-                                        ch: symbol,
-                                        pos: Pos(x as XY, y as XY),
-                                    })
-                                }
-                            }
-                        }
-                    });
-                if !num_buffer.is_empty() {
-                    nums.push(Num {
-                        repl: num_buffer.clone(),
-                        pos: Pos((line.len() - num_buffer.len()) as XY, y as XY),
-                    });
-                    num_buffer.clear();
+        for (y, line) in raw_input.lines().enumerate() {
+            let mut start = None;
+            for (x, ch) in line.char_indices().chain([(line.len(), '.')]) {
+                match (ch.is_ascii_digit(), start) {
+                    (true, None) => start = Some(x),
+                    (false, Some(from)) => {
+                        let repl = &line[from..x];
+                        let val = number(repl).map_err(|err| err.at(y + 1, line))?;
+                        nums.push(Num { repl, val, pos: Point::new(from, y) });
+                        start = None;
+                    }
+                    _ => (),
                 }
-            });
-        Schematic { nums, symbols }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_example_part1() {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..".lines().map(|l| l.to_string()).collect::<Vec<String>>();
-        let input = Day::parse_input(&input);
-        assert_eq!(Day::part1(&input), 4361);
-    }
-
-    #[test]
-    fn test_example_part2() {
-        let input = "12.......*..
-+.........34
-.......-12..
-..78........
-..*....60...
-78.........9
-.5.....23..$
-8...90*12...
-............
-2.2......12.
-.*.........*
-1.1..503+.56".lines().map(|l| l.to_string()).collect::<Vec<String>>();
-        let input = Day::parse_input(&input);
-        assert_eq!(Day::part1(&input), 925);
+            }
+        }
+        Ok(Schematic { grid: Grid::parse(raw_input, Ok)?, nums })
     }
 }
@@ -1,6 +1,7 @@
-use std::cmp;
 use std::str::FromStr;
 use itertools::{Itertools, PeekingNext};
+use crate::commons::IntervalSet;
+use crate::err::{number, AocError};
 use crate::util::Solution;
 
 
@@ -14,24 +15,26 @@ struct Range {
 }
 
 impl FromStr for Range {
-    type Err = ();
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.split_whitespace();
-        let dst = s.next().unwrap().parse::<Num>().unwrap();
-        let src = s.next().unwrap().parse::<Num>().unwrap();
-        let len = s.next().unwrap().parse::<Num>().unwrap();
+        let mut nums = s.split_whitespace();
+        let mut next = || nums.next()
+            .ok_or(AocError::WrongFormat(s.to_string()))
+            .and_then(number::<Num>);
+        let dst = next()?;
+        let src = next()?;
+        let len = next()?;
         Ok(Range { src, dst, len })
     }
 }
 
 #[derive(Debug)]
 pub struct Map {
-    src: String,
-    dst: String,
     ranges: Vec<Range>,
 }
 
+/// Categories the maps translate through one after another, the last map ends at `location`
 const ORDER: [&str; 7] = [
     "seed", "soil", "fertilizer",
     "water", "light", "temperature",
@@ -45,21 +48,33 @@ pub struct World {
 }
 
 impl Map {
-    fn from_str(s: Vec<String>) -> Self {
-        let mut s = s.into_iter();
-        let map_dsc = s.next().unwrap();
-        let mut map_dsc = map_dsc
-            .split_whitespace().next().unwrap()
-            .split('-');
-        let src = map_dsc.next().unwrap().to_string();
-        let dst = map_dsc.skip(1).next().unwrap().to_string();
-
-        let ranges = s.into_iter()
-            .map(|line| Range::from_str(line.as_str()).unwrap())
+    /// Parses the `idx`th map from its lines, each with its 1-based line number, the map has to
+    /// translate the category `ORDER` expects at that point
+    fn from_str(idx: usize, s: &[(usize, &str)]) -> Result<Self, AocError> {
+        let mut s = s.iter().copied();
+        let (line, map_dsc) = s.next().ok_or(AocError::WrongFormat("empty map".to_string()))?;
+        let wrong_format = || AocError::WrongFormat(map_dsc.to_string()).at(line, map_dsc);
+        let name = map_dsc
+            .split_whitespace().next()
+            .ok_or_else(wrong_format)?;
+        let mut names = name.split('-');
+        let src = names.next()
+            .ok_or_else(wrong_format)?;
+        let dst = names.nth(1)
+            .ok_or_else(wrong_format)?;
+        let expected = ORDER.get(idx).map(|&src| (src, ORDER.get(idx + 1).copied().unwrap_or("location")));
+        if expected != Some((src, dst)) {
+            return Err(AocError::Invalid("map", name.to_string()).at(line, map_dsc));
+        }
+
+        let ranges = s
+            .map(|(line, range)| Range::from_str(range).map_err(|err| err.at(line, range)))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .sorted_by(|a, b| a.src.cmp(&b.src))
             .collect();
 
-        Map { src, dst, ranges }
+        Ok(Map { ranges })
     }
 
     fn translate(&self, input: Num) -> Num {
@@ -70,59 +85,40 @@ impl Map {
         }
         input
     }
+
+    /// Translates every seed of the set at once, seeds outside the ranges of the map keep their
+    /// number
+    fn translate_set(&self, seeds: &IntervalSet<Num>) -> IntervalSet<Num> {
+        let sources = self.ranges.iter()
+            .map(|range| range.src..range.src + range.len)
+            .collect::<IntervalSet<_>>();
+        self.ranges.iter()
+            .map(|range| seeds
+                .intersection(&IntervalSet::from(range.src..range.src + range.len))
+                .shift(range.src, range.dst))
+            .fold(seeds.difference(&sources), |translated, moved| translated.union(&moved))
+    }
 }
 
 pub struct Day;
 
 
 impl Day {
-    fn group_seeds(seeds: Vec<Num>) -> Vec<(Num, Num)> {
-        let mut starts = Vec::new();
-        let mut ends = Vec::new();
-        seeds.chunks(2).for_each(|chunk| {
-            starts.push(chunk[0]);
-            ends.push(chunk[0] + chunk[1]);
-        });
-        starts.sort();
-        ends.sort();
-        let mut starts = starts.into_iter().peekable();
-        let mut ends = ends.into_iter().peekable();
-
-        let mut optim_ranges = Vec::new();
-        let mut open = 0;
-        let mut range = Vec::new();
-        loop {
-            let start = starts.peek();
-            let end = ends.peek();
-            if start.is_none() {
-                optim_ranges.push((range[0], ends.last().unwrap()));
-                break;
-            } else if start < end {
-                let tmp = starts.next().unwrap();
-                if open == 0 {
-                    range.push(tmp);
-                }
-                open += 1;
-            } else {
-                open -= 1;
-                let tmp = ends.next().unwrap();
-                if open == 0 {
-                    range.push(tmp);
-                    optim_ranges.push((range[0], range[1]));
-                    range.clear();
-                }
-            }
-        }
-        optim_ranges
+    /// Seed numbers come in pairs of the first seed and the number of seeds
+    fn group_seeds(seeds: &[Num]) -> IntervalSet<Num> {
+        seeds.chunks_exact(2)
+            .map(|chunk| chunk[0]..chunk[0] + chunk[1])
+            .collect()
     }
 }
 
 impl<'a> Solution<'a> for Day {
     type Input = World;
-    type Output = Num;
-    const DAY: &'a str = "Day05";
+    type Output1 = Num;
+    type Output2 = Num;
+    const DAY: &'static str = "Day05";
 
-    fn part1(input: &Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
         let seeds: Vec<_> = input.seeds.iter()
             .map(|seed| {
                 let mut seed = seed.clone();
@@ -131,35 +127,38 @@ impl<'a> Solution<'a> for Day {
                 }
                 seed
             }).collect();
-        *seeds.iter().min().unwrap()
+        seeds.into_iter().min()
+            .ok_or(AocError::NoSolution("no seeds".to_string()))
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
-        let seeds_ranges = Self::group_seeds(input.seeds.clone());
-        let mut min_seed = Num::MAX;
-        for seed_range in seeds_ranges.iter() {
-            for seed in seed_range.0..seed_range.1 {
-                let mut seed = seed;
-                for map in input.maps.iter() {
-                    seed = map.translate(seed);
-                }
-                min_seed = cmp::min(min_seed, seed);
-            }
-        }
-        min_seed
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
+        input.maps.iter()
+            .fold(Self::group_seeds(&input.seeds), |seeds, map| map.translate_set(&seeds))
+            .min()
+            .ok_or(AocError::NoSolution("no seeds".to_string()))
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        let seeds_str = raw_input.get(0).unwrap();
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        let lines = raw_input.lines().collect::<Vec<_>>();
+        let seeds_str = lines.first()
+            .ok_or(AocError::WrongFormat("missing seeds".to_string()))?;
         let seeds = seeds_str.split(':').skip(1)
-            .map(|seeds| seeds.split_whitespace())
-            .flatten()
-            .map(|seed| seed.parse::<Num>().unwrap())
-            .collect();
-        let ranges = raw_input[2..].split(|line| line.is_empty())
-            .map(|lines| Map::from_str(lines.to_vec()))
-            .collect();
-        World { seeds, maps: ranges }
+            .flat_map(|seeds| seeds.split_whitespace())
+            .map(|seed| number::<Num>(seed).map_err(|err| err.at(1, seeds_str)))
+            .collect::<Result<_, _>>()?;
+        let numbered = lines.iter().copied().enumerate()
+            .skip(2)
+            .map(|(idx, line)| (idx + 1, line))
+            .collect::<Vec<_>>();
+        let maps = numbered
+            .split(|(_, line)| line.is_empty())
+            .enumerate()
+            .map(|(idx, map)| Map::from_str(idx, map))
+            .collect::<Result<Vec<_>, _>>()?;
+        if maps.len() < ORDER.len() {
+            return Err(AocError::WrongFormat(format!("missing the map from {}", ORDER[maps.len()])));
+        }
+        Ok(World { seeds, maps })
     }
 }
 
@@ -167,62 +166,20 @@ impl<'a> Solution<'a> for Day {
 mod tests {
     use super::*;
 
-    fn get_input() -> Vec<String> {
-        "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4".lines()
-            .map(|line| line.to_string())
-            .collect::<Vec<_>>()
-    }
-
-    #[test]
-    fn test_example_t1() {
-        let input = get_input();
-        let input = Day::parse_input(&input);
-        assert_eq!(Day::part1(&input), 35);
-    }
-
     #[test]
-    fn test_example_t2() {
-        let input = get_input();
-        let input = Day::parse_input(&input);
-        assert_eq!(Day::part2(&input), 46);
+    fn test_intervals() {
+        let input = vec![1, 2, 2, 4, 8, 2, 15, 3];
+        let input = Day::group_seeds(&input);
+        assert_eq!(input.ranges(), &[1..6, 8..10, 15..18]);
     }
 
     #[test]
-    fn test_intervals() {
-        let input = vec![1, 2, 2, 4, 8, 2, 15, 3];
-        let input = Day::group_seeds(input);
-        assert_eq!(input, vec![(1, 6), (8, 10), (15, 18)]);
+    fn test_map_order() {
+        let input = "seeds: 1\n\nsoil-to-fertilizer map:\n1 2 3";
+        let err = Day::parse_input(input).unwrap_err();
+        assert!(matches!(&err, AocError::Parse(_)), "{:?}", err);
+        assert!(err.to_string().contains("Invalid map: soil-to-fertilizer"), "{}", err);
+        let err = Day::parse_input("seeds: 1\n\nseed-to-soil map:\n1 2 3").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: missing the map from soil");
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use crate::err::{number, AocError};
 use crate::util::Solution;
 
 
@@ -30,69 +31,44 @@ pub struct Day;
 
 impl<'a> Solution<'a> for Day {
     type Input = Vec<Race>;
-    type Output = Num;
-    const DAY: &'a str = "Day06";
+    type Output1 = Num;
+    type Output2 = Num;
+    const DAY: &'static str = "Day06";
 
-    fn part1(input: &Self::Input) -> Self::Output {
-        println!("{:?}", input);
-        input.iter()
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
+        Ok(input.iter()
             .map(|r| r.zeros())
             .map(|(l, u)| u - l + 1)
-            .product::<Num>()
+            .product::<Num>())
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
         let (time, dist) = input.into_iter()
             .fold(("".to_string(), "".to_string()), |acc, r| {
                 (format!("{}{}", acc.0, r.time), format!("{}{}", acc.1, r.distance))
             });
 
         let race = Race {
-            time: time.parse().unwrap(),
-            distance: dist.parse().unwrap(),
+            time: time.parse()?,
+            distance: dist.parse()?,
         };
 
-        println!("{:?}", race);
-
         let (l, u) = race.zeros();
-        u - l + 1
+        Ok(u - l + 1)
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        raw_input[0].split_whitespace()
-            .zip(raw_input[1].split_whitespace())
-            .skip(1)
-            .map(|(t, std)| {
-                Race {
-                    time: t.parse().unwrap(),
-                    distance: std.parse().unwrap(),
-                }
-            }).collect()
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        if let [times, distances] = raw_input.lines().collect::<Vec<_>>()[..] {
+            return times.split_whitespace()
+                .zip(distances.split_whitespace())
+                .skip(1)
+                .map(|(t, std)| {
+                    Ok(Race {
+                        time: number(t).map_err(|err| err.at(1, times))?,
+                        distance: number(std).map_err(|err| err.at(2, distances))?,
+                    })
+                }).collect();
+        }
+        Err(AocError::WrongFormat("expected time and distance lines".to_string()))
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn get_input() -> Vec<String> {
-        vec![
-            "Time:      7  15   30".to_string(),
-            "Distance:  9  40  200".to_string(),
-        ]
-    }
-
-    #[test]
-    fn test_example_t1() {
-        let input = get_input();
-        let input = Day::parse_input(&input);
-        assert_eq!(Day::part1(&input), 288);
-    }
-
-    #[test]
-    fn test_example_t2() {
-        let input = get_input();
-        let input = Day::parse_input(&input);
-        assert_eq!(Day::part2(&input), 71503);
-    }
-}
\ No newline at end of file
@@ -1,8 +1,8 @@
 use std::collections::HashSet;
 use std::str::FromStr;
 use itertools::Itertools;
-use crate::err::InputError;
-use crate::util::Solution;
+use crate::err::{number, AocError};
+use crate::util::{parse_lines, Solution};
 
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug)]
@@ -32,7 +32,7 @@ impl Card {
 }
 
 impl FromStr for Card {
-    type Err = InputError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -49,7 +49,7 @@ impl FromStr for Card {
             "4" => Ok(Card::Four),
             "3" => Ok(Card::Three),
             "2" => Ok(Card::Two),
-            _ => Err(InputError::WrongFormat(format!("Invalid card: {}", s)))
+            _ => Err(AocError::Invalid("card", s.to_string()))
         }
     }
 }
@@ -149,20 +149,19 @@ impl PartialEq for Hand {
 impl Eq for Hand {}
 
 impl FromStr for Hand {
-    type Err = InputError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let [cards, bid] = s.trim().split_whitespace().collect::<Vec<&str>>()[..] {
-            let bid = bid.parse().unwrap();
+            let bid = number(bid)?;
             let cards = cards.chars()
                 .map(|c| c.to_string().parse())
-                .collect::<Result<Vec<Card>, _>>()?;
-            return Ok(Hand {
-                cards: [cards[0], cards[1], cards[2], cards[3], cards[4]],
-                bid,
-            });
+                .collect::<Result<Vec<Card>, _>>()?
+                .try_into()
+                .map_err(|_| AocError::Invalid("hand", cards.to_string()))?;
+            return Ok(Hand { cards, bid });
         }
-        Err(InputError::WrongFormat("Invalid hand".to_string()))
+        Err(AocError::Invalid("hand", s.to_string()))
     }
 }
 
@@ -219,11 +218,11 @@ impl Eq for HandJ {}
 
 
 impl FromStr for HandJ {
-    type Err = InputError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let [cards, bid] = s.trim().split_whitespace().collect::<Vec<&str>>()[..] {
-            let bid = bid.parse().unwrap();
+            let bid = number(bid)?;
             let cards = cards.chars()
                 .map(|c| c.to_string().parse())
                 .collect::<Result<Vec<Card>, _>>()?;
@@ -232,7 +231,7 @@ impl FromStr for HandJ {
                 bid,
             });
         }
-        Err(InputError::WrongFormat("Invalid hand".to_string()))
+        Err(AocError::Invalid("hand", s.to_string()))
     }
 }
 
@@ -259,53 +258,29 @@ impl Day {
 
 impl<'a> Solution<'a> for Day {
     type Input = Vec<Hand>;
-    type Output = u32;
-    const DAY: &'a str = "Day07";
+    type Output1 = u32;
+    type Output2 = u32;
+    const DAY: &'static str = "Day07";
 
-    fn part1(input: &Self::Input) -> Self::Output {
-        input.iter()
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
+        Ok(input.iter()
             .sorted_by(|h1, h2| h2.cmp(&h1))
             .enumerate()
             .map(|(i, h)| (i as u32 + 1) * h.bid)
-            .sum::<u32>()
+            .sum::<u32>())
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
-        input.iter()
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
+        Ok(input.iter()
             .map(|h| Hand { cards: h.cards, bid: h.bid })
             .map(|h| HandJ::from(h))
             .sorted_by(|h1, h2| h2.cmp(&h1))
             .enumerate()
             .map(|(i, h)| (i as u32 + 1) * h.bid)
-            .sum::<u32>()
+            .sum::<u32>())
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        raw_input.iter()
-            .map(|s| s.parse().unwrap())
-            .collect()
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        parse_lines(raw_input, Hand::from_str)
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn get_input() -> Vec<String> {
-        "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483".lines().map(|l| l.to_string()).collect()
-    }
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(Day::part1(&Day::parse_input(&get_input())), 6440);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(Day::part2(&Day::parse_input(&get_input())), 5905);
-    }
-}
\ No newline at end of file
@@ -1,50 +1,59 @@
 use std::collections::HashMap;
-use std::str::FromStr;
+use itertools::Itertools;
 use regex::Regex;
-use crate::commons;
+use crate::commons::{number, Direction};
+use crate::err::AocError;
 use crate::util::Solution;
 
 
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    Left,
-    Right
-}
-
-impl FromStr for Direction {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "L" => Ok(Direction::Left),
-            "R" => Ok(Direction::Right),
-            _ => Err(())
-        }
-    }
-}
-
-
-pub struct Desert {
+pub struct Desert<'a> {
     instructions: Vec<Direction>,
-    map: HashMap<String, (String, String)>,
+    map: HashMap<&'a str, (&'a str, &'a str)>,
 }
 
-impl Desert {
+impl<'a> Desert<'a> {
 
-    fn count_steps(&self, start: &str, end_cond: &dyn Fn(&str) -> bool) -> usize {
-        let mut instructions = self.instructions.iter().cycle();
-        let mut steps = 0;
+    /// Walks from `start` following the instructions from `offset` on until `end_cond` holds,
+    /// taking at least one step. Gives the number of steps and the node it stopped at. A walk
+    /// that runs through every node at every instruction without reaching an end never will
+    fn walk(&self, start: &'a str, offset: usize, end_cond: &dyn Fn(&str) -> bool) -> Result<(usize, &'a str), AocError> {
+        let mut instructions = self.instructions.iter().cycle().skip(offset % self.instructions.len().max(1));
         let mut pos = start;
-        while !end_cond(pos) {
-            let dir = instructions.next().unwrap();
-            let (left, right) = self.map.get(pos).unwrap();
+        for steps in 1..=self.map.len() * self.instructions.len() {
+            let dir = instructions.next()
+                .ok_or(AocError::NoSolution("no instructions".to_string()))?;
+            let (left, right) = self.map.get(pos)
+                .ok_or(AocError::NoSolution(format!("dead end at {}", pos)))?;
             pos = match dir {
                 Direction::Left => left,
-                Direction::Right => right
+                Direction::Right => right,
+                other => return Err(AocError::Invalid("direction", other.to_string())),
             };
-            steps += 1;
+            if end_cond(pos) {
+                return Ok((steps, pos));
+            }
+        }
+        Err(AocError::NoSolution(format!("no end is reachable from {}", start)))
+    }
+
+    /// Steps at which the ghost from `start` is at an end during its first loop, and the length
+    /// of that loop. The loop closes once the ghost is back at its first end at the same
+    /// instruction, so every later end it reaches is one of these plus a multiple of the length
+    fn cycle(&self, start: &'a str, end_cond: &dyn Fn(&str) -> bool) -> Result<(Vec<usize>, usize), AocError> {
+        let (first, end) = self.walk(start, 0, end_cond)?;
+        let mut hits = vec![first];
+        let (mut steps, mut pos) = (first, end);
+        loop {
+            let (more, next) = self.walk(pos, steps, end_cond)?;
+            (steps, pos) = (steps + more, next);
+            if pos == end && (steps - first) % self.instructions.len() == 0 {
+                return Ok((hits, steps - first));
+            }
+            if hits.len() > self.map.len() * self.instructions.len() {
+                return Err(AocError::NoSolution(format!("ghost from {} never loops", start)));
+            }
+            hits.push(steps);
         }
-        steps
     }
 
 }
@@ -57,43 +66,65 @@ const END: &'static str = "ZZZ";
 
 
 impl<'a> Solution<'a> for Day {
-    type Input = Desert;
-    type Output = usize;
-    const DAY: &'a str = "Day08";
+    type Input = Desert<'a>;
+    type Output1 = usize;
+    type Output2 = usize;
+    const DAY: &'static str = "Day08";
 
-    fn part1(input: &Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
         let end_cond = |s: &str| s == END;
-        input.count_steps(START, &end_cond)
+        input.walk(START, 0, &end_cond).map(|(steps, _)| steps)
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
         let end_cond = |s: &str| s.chars().last() == Some('Z');
-        let steps = input.map.iter()
-            .filter(|(k, _)| k.chars().last() == Some('A'))
-            .map(|(k, _)| input.count_steps(k, &end_cond))
-            .collect::<Vec<_>>();
-        commons::lcm(steps)
+        let cycles = input.map.keys()
+            .filter(|k| k.ends_with('A'))
+            .map(|k| input.cycle(k, &end_cond))
+            .collect::<Result<Vec<_>, _>>()?;
+        if cycles.is_empty() {
+            return Err(AocError::NoSolution("no starting nodes".to_string()));
+        }
+        // every ghost picks one of its ends, the earliest step where the picks line up wins
+        cycles.iter()
+            .map(|(hits, period)| hits.iter().map(move |&hit| (hit, *period)))
+            .multi_cartesian_product()
+            .filter_map(|picks| {
+                let latest = picks.iter().map(|&(hit, _)| hit).max()?;
+                let congruences = picks.iter().map(|&(hit, period)| (hit as i64, period as i64));
+                let (remainder, period) = number::crt(congruences)?;
+                let (remainder, period) = (remainder as usize, period as usize);
+                Some(remainder + latest.saturating_sub(remainder).div_ceil(period) * period)
+            })
+            .min()
+            .ok_or(AocError::NoSolution("ghosts never reach the ends together".to_string()))
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        let mut input = raw_input.iter();
-        let instructions = input.next().unwrap()
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        let mut input = raw_input.lines();
+        let first = input.next()
+            .ok_or(AocError::WrongFormat("missing instructions".to_string()))?;
+        let instructions = first
             .chars()
-            .map(|c| c.to_string().parse())
-            .collect::<Result<Vec<Direction>, _>>().unwrap();
+            .map(|c| match Direction::from_letter(c) {
+                Some(dir @ (Direction::Left | Direction::Right)) => Ok(dir),
+                _ => Err(AocError::Invalid("direction", c.to_string())),
+            })
+            .collect::<Result<Vec<Direction>, _>>()
+            .map_err(|err| err.at(1, first))?;
 
         let re = Regex::new(r"^(.{3})\s=\s\((.{3}),\s(.{3})\)$").unwrap();
-        let map = input.skip(1).map(|s| {
-            let caps = re.captures(s).unwrap();
-            let key = caps.get(1).unwrap().as_str().to_string();
-            let left = caps.get(2).unwrap().as_str().to_string();
-            let right = caps.get(3).unwrap().as_str().to_string();
-            (key, (left, right))
-        }).collect();
-
-        Desert {
+        let map = input.enumerate().skip(1).map(|(idx, s)| {
+            let caps = re.captures(s)
+                .ok_or_else(|| AocError::WrongFormat(s.to_string()).at(idx + 2, s))?;
+            let field = |idx| caps.get(idx).map_or("", |m| m.as_str());
+            let (key, left, right) = (field(1), field(2), field(3));
+            Ok((key, (left, right)))
+        }).collect::<Result<_, AocError>>()?;
+
+        Ok(Desert {
             instructions,
             map
-        }
+        })
     }
 }
\ No newline at end of file
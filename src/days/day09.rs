@@ -1,4 +1,5 @@
-use crate::util::Solution;
+use crate::err::{number, AocError};
+use crate::util::{parse_lines, Solution};
 
 
 type Num = i32;
@@ -6,66 +7,52 @@ type Num = i32;
 pub struct Day;
 
 impl Day {
-    fn interpolate(vec: Vec<Num>) -> Vec<Num> {
-        let mut last = vec![vec.last().unwrap().to_owned()];
-        let mut intermediate = vec.clone();
-        while intermediate.iter().any(|&x| x != 0) {
-            intermediate = intermediate.windows(2).into_iter()
+    /// Next value of `history`, the sum of the last values of it and of every row of
+    /// differences below it
+    fn interpolate(history: &[Num]) -> Num {
+        let mut next = 0;
+        let mut row = history.to_vec();
+        while let Some(&last) = row.last() {
+            if row.iter().all(|&x| x == 0) {
+                break;
+            }
+            next += last;
+            row = row.windows(2)
                 .map(|w| w[1] - w[0])
                 .collect::<Vec<Num>>();
-            last.push(intermediate.last().unwrap().to_owned());
         }
-        let mut vec = vec.clone();
-        vec.push(last.iter().sum::<Num>());
-        vec
+        next
     }
 }
 
 impl<'a> Solution<'a> for Day {
     type Input = Vec<Vec<Num>>;
-    type Output = Num;
-    const DAY: &'a str = "Day09";
+    type Output1 = Num;
+    type Output2 = Num;
+    const DAY: &'static str = "Day09";
 
-    fn part1(input: &Self::Input) -> Self::Output {
-        input.iter()
-            .map(|line| Day::interpolate(line.to_owned()))
-            .map(|vec| vec.last().unwrap().to_owned())
-            .sum()
-    }
-
-    fn part2(input: &Self::Input) -> Self::Output {
-        input.iter()
-            .map(|line| line.iter().rev().map(|item| *item).collect())
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
+        Ok(input.iter()
             .map(|line| Day::interpolate(line))
-            .map(|vec| vec.last().unwrap().to_owned())
-            .sum()
+            .sum())
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        raw_input.into_iter()
-            .map(|line|
-                line.split_whitespace()
-                    .map(|num| num.parse::<Num>().unwrap())
-                    .collect::<Vec<Num>>()
-            ).collect::<Vec<Vec<Num>>>()
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
+        Ok(input.iter()
+            .map(|line| line.iter().rev().copied().collect::<Vec<Num>>())
+            .map(|line| Day::interpolate(&line))
+            .sum())
     }
-}
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    fn get_input() -> Vec<String> {
-        "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45".lines().map(|s| s.to_string()).collect()
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        parse_lines(raw_input, |line| {
+            let history = line.split_whitespace()
+                .map(number::<Num>)
+                .collect::<Result<Vec<Num>, _>>()?;
+            if history.is_empty() {
+                return Err(AocError::WrongFormat("empty history".to_string()));
+            }
+            Ok(history)
+        })
     }
-
-    #[test]
-    fn test_part1() {
-        let input = Day::parse_input(&get_input());
-        assert_eq!(Day::part1(&input), 114);
-    }
-
-}
\ No newline at end of file
+}
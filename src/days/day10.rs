@@ -1,20 +1,10 @@
-use std::collections::{HashMap, VecDeque};
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
-use crate::err::InputError;
+use crate::commons::{search, Direction, Grid, Point};
+use crate::err::AocError;
 use crate::util::Solution;
 
 
-type Coord = (isize, isize);
-
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug)]
 enum Pipe {
     Horizontal,
@@ -28,7 +18,7 @@ enum Pipe {
 }
 
 impl FromStr for Pipe {
-    type Err = InputError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -40,22 +30,39 @@ impl FromStr for Pipe {
             "F" => Ok(Pipe::BottomRight),
             "S" => Ok(Pipe::Start),
             "." => Ok(Pipe::None),
-            _ => Err(InputError::WrongFormat(format!("Invalid pipe: {}", s)))
+            _ => Err(AocError::Invalid("pipe", s.to_string()))
         }
     }
 }
 
+impl Display for Pipe {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ch = match self {
+            Pipe::Horizontal => '-',
+            Pipe::Vertical => '|',
+            Pipe::TopLeft => 'J',
+            Pipe::TopRight => 'L',
+            Pipe::BottomLeft => '7',
+            Pipe::BottomRight => 'F',
+            Pipe::Start => 'S',
+            Pipe::None => '.',
+        };
+        write!(f, "{}", ch)
+    }
+}
+
 impl Pipe {
-    fn connected(&self, other: &Self, dir: &Direction) -> bool {
+    /// Whether a step in `dir` leads from this pipe into `other`
+    fn connected(&self, other: &Self, dir: Direction) -> bool {
         match dir {
-            Direction::Down => {
+            Direction::Up => {
                 match (self, other) {
                     (Pipe::Vertical | Pipe::Start | Pipe::TopLeft | Pipe::TopRight,
                         Pipe::BottomLeft | Pipe::BottomRight | Pipe::Vertical) => true,
                     _ => false
                 }
             }
-            Direction::Up => {
+            Direction::Down => {
                 match (self, other) {
                     (Pipe::Vertical | Pipe::Start | Pipe::BottomLeft | Pipe::BottomRight,
                         Pipe::TopLeft | Pipe::TopRight | Pipe::Vertical) => true,
@@ -82,91 +89,50 @@ impl Pipe {
 
 
 pub struct Maze {
-    map: HashMap<Coord, Pipe>,
-    start: Coord,
+    grid: Grid<Pipe>,
+    start: Point<usize>,
 }
 
 impl Debug for Maze {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let (max_x, max_y) = self.size();
-        writeln!(f, " :{}", (0..=max_x)
-            .map(|i| ((i % 10) as u8 + 48) as char)
-            .collect::<String>())?;
-        for y in 0..=max_y {
-            write!(f, "{}:", y % 10)?;
-            for x in 0..=max_x {
-                if let Some(pipe) = self.map.get(&(x, y)) {
-                    match pipe {
-                        Pipe::Horizontal => write!(f, "-")?,
-                        Pipe::Vertical => write!(f, "|")?,
-                        Pipe::TopLeft => write!(f, "J")?,
-                        Pipe::TopRight => write!(f, "L")?,
-                        Pipe::BottomLeft => write!(f, "7")?,
-                        Pipe::BottomRight => write!(f, "F")?,
-                        Pipe::Start => write!(f, "S")?,
-                        Pipe::None => write!(f, ".")?,
-                    }
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.grid)
     }
 }
 
 impl Maze {
-    fn neighbours(&self, coord: Coord) -> Vec<Coord> {
-        let (x, y) = coord;
-        if let Some(pipe) = self.map.get(&(x, y)) {
-            return vec![
-                ((coord.0, coord.1 + 1), Direction::Up),
-                ((coord.0, coord.1 - 1), Direction::Down),
-                ((coord.0 - 1, coord.1), Direction::Left),
-                ((coord.0 + 1, coord.1), Direction::Right),
-            ]
-                .into_iter()
-                .filter(|(coord, dir)| {
-                    if let Some(other) = self.map.get(coord) {
-                        pipe.connected(other, dir)
-                    } else {
-                        false
-                    }
-                })
-                .map(|(coord, _)| coord)
-                .collect();
-        }
-        Vec::new()
-    }
-
-    fn cycle(&self, start: Coord) -> Vec<Coord> {
-        let mut queue = VecDeque::from(vec![(start, (-1, -1))]);
-        let mut visited = vec![start];
-        while let Some((node, _)) = queue.pop_front() {
-            for neighbour in self.neighbours(node) {
-                if visited.contains(&neighbour) && Maze::already_seen(&queue, (neighbour, node)) {
-                    return visited;
-                }
-                if !visited.contains(&neighbour) {
-                    visited.push(neighbour);
-                    queue.push_back((neighbour, node));
-                }
-            }
-        }
-        visited
+    fn neighbours(&self, coord: Point<usize>) -> Vec<Point<usize>> {
+        let pipe = &self.grid[coord];
+        Direction::ALL.into_iter()
+            .filter_map(|dir| Some((dir, self.grid.step(coord, dir)?)))
+            .filter(|&(dir, next)| pipe.connected(&self.grid[next], dir))
+            .map(|(_, next)| next)
+            .collect()
     }
 
-    fn already_seen(input: &VecDeque<(Coord, Coord)>, item: (Coord, Coord)) -> bool {
-        for (child, parent) in input {
-            if child == &item.0 && parent != &item.1 {
-                return true;
-            }
-        }
-        false
+    /// Pipes of the loop through the start in walking order, beginning at the start. Both ways
+    /// round meet at the pipe farthest from the start
+    fn main_loop(&self) -> Result<Vec<Point<usize>>, AocError> {
+        let search = search::bfs(self.start, |coord| self.neighbours(*coord), |_| false);
+        let (&far, &distance) = search.distances().iter()
+            .max_by_key(|(_, distance)| **distance)
+            .ok_or(AocError::NoSolution("empty maze".to_string()))?;
+        let ends = self.neighbours(far).into_iter()
+            .filter(|coord| distance > 0 && search.distance(coord) == Some(distance - 1))
+            .collect::<Vec<_>>();
+        let (Some(mut path), Some(mut back)) = (
+            ends.first().and_then(|end| search.path_to(end)),
+            ends.get(1).and_then(|end| search.path_to(end)),
+        ) else {
+            return Err(AocError::NoSolution("no loop through the start".to_string()));
+        };
+        path.push(far);
+        back.reverse();
+        back.pop();
+        path.append(&mut back);
+        Ok(path)
     }
 
-    fn area(&self, input: &Vec<Coord>) -> usize {
+    fn area(&self, input: &[Point<usize>]) -> usize {
         let corners = vec![
             Pipe::Start,
             Pipe::TopLeft,
@@ -175,108 +141,46 @@ impl Maze {
             Pipe::BottomRight,
         ];
 
-        let mut path = self.path(input).into_iter()
-            .filter(|coord| corners.contains(self.map.get(coord).unwrap()))
-            .collect::<Vec<Coord>>();
+        let mut path = input.iter().copied()
+            .filter(|coord| corners.contains(&self.grid[*coord]))
+            .collect::<Vec<Point<usize>>>();
 
         path.push(path[0]);
 
         let a = path.windows(2)
             .map(|window| {
-                let (x1, y1) = window[0];
-                let (x2, y2) = window[1];
+                let Point { x: x1, y: y1 } = Point::<isize>::from(window[0]);
+                let Point { x: x2, y: y2 } = Point::<isize>::from(window[1]);
                 let x = (x1 * y2) - (y1 * x2);
                 x
             }).sum::<isize>().abs() as usize / 2;
         a - (input.len() / 2) + 1
     }
-
-    fn path(&self, input: &Vec<Coord>) -> Vec<Coord> {
-        let mut path = vec![input[0]];
-        let mut seen = vec![input[0]];
-        while seen.len() != input.len() {
-            for coord in input {
-                if seen.contains(coord) {
-                    continue;
-                }
-                if let Some(last) = path.last() {
-                    if self.neighbours(*last).contains(coord) {
-                        path.push(*coord);
-                        seen.push(*coord);
-                    }
-                }
-            }
-        }
-        path
-    }
-
-    fn size(&self) -> Coord {
-        let mut max_x = 0;
-        let mut max_y = 0;
-        for (x, y) in self.map.keys() {
-            if *x > max_x {
-                max_x = *x;
-            }
-            if *y > max_y {
-                max_y = *y;
-            }
-        }
-        (max_x, max_y)
-    }
 }
 
 pub struct Day;
 
 impl<'a> Solution<'a> for Day {
     type Input = Maze;
-    type Output = usize;
-    const DAY: &'a str = "Day10";
+    type Output1 = usize;
+    type Output2 = usize;
+    const DAY: &'static str = "Day10";
 
-    fn part1(input: &Self::Input) -> Self::Output {
-        let path = input.cycle(input.start);
-        path.len() / 2
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
+        Ok(input.main_loop()?.len() / 2)
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
-        let path = input.cycle(input.start);
-        input.area(&path)
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
+        Ok(input.area(&input.main_loop()?))
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        let mut start = (0, 0);
-        let mut map = HashMap::new();
-        for (y, line) in raw_input.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let coord = (x as isize, y as isize);
-                let pipe = Pipe::from_str(&c.to_string()).unwrap();
-                if pipe == Pipe::Start {
-                    start = coord;
-                }
-                if pipe != Pipe::None {
-                    map.insert(coord, pipe);
-                }
-            }
-        }
-        Maze { map, start }
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        let grid = Grid::parse(raw_input, |c| Pipe::from_str(&c.to_string()))?;
+        let start = grid.iter()
+            .find(|(_, pipe)| **pipe == Pipe::Start)
+            .map(|(coord, _)| coord)
+            .ok_or_else(|| AocError::WrongFormat("no start tile S".to_string())
+                .at(1, raw_input.lines().next().unwrap_or("")))?;
+        Ok(Maze { grid, start })
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn get_input() -> Vec<String> {
-        "..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...".lines().map(|s| s.to_string()).collect()
-    }
-
-    #[test]
-    fn test_part1() {
-        let input = &Day::parse_input(&get_input());
-        println!("{:?}", input);
-        assert_eq!(Day::part1(input), 4);
-    }
-}
\ No newline at end of file
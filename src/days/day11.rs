@@ -1,59 +1,52 @@
 use std::fmt::{Debug, Formatter};
 use itertools::Itertools;
+use crate::commons::{Grid, Point};
+use crate::err::AocError;
 use crate::util::Solution;
 
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, Debug)]
-struct Pos(isize, isize);
-
-impl Pos {
-    fn distance(&self, other: &Self) -> isize {
-        (self.0 - other.0).abs() + (self.1 - other.1).abs()
-    }
-}
-
 pub struct Space {
-    galaxies: Vec<Pos>
+    galaxies: Vec<Point<isize>>
 }
 
 impl Space {
     fn expand(&mut self, expansion: isize) {
         let mut new_galaxies = self.galaxies.clone();
-        new_galaxies.sort_by(|a, b| a.0.cmp(&b.0));
+        new_galaxies.sort_by_key(|galaxy| galaxy.x);
         let last_idx = new_galaxies.len() - 1;
         let mut add = 0;
         for idx in 0..last_idx {
-            let diff = std::cmp::max(new_galaxies[idx+1].0 - new_galaxies[idx].0 - 1, 0);
-            new_galaxies[idx].0 += add;
+            let diff = std::cmp::max(new_galaxies[idx+1].x - new_galaxies[idx].x - 1, 0);
+            new_galaxies[idx].x += add;
             add += diff * (expansion - 1);
         }
-        new_galaxies[last_idx].0 += add;
+        new_galaxies[last_idx].x += add;
 
-        new_galaxies.sort_by(|a, b| a.1.cmp(&b.1));
+        new_galaxies.sort_by_key(|galaxy| galaxy.y);
         let mut add = 0;
         for idx in 0..last_idx {
-            let diff = std::cmp::max(new_galaxies[idx+1].1 - new_galaxies[idx].1 - 1, 0);
-            new_galaxies[idx].1 += add;
+            let diff = std::cmp::max(new_galaxies[idx+1].y - new_galaxies[idx].y - 1, 0);
+            new_galaxies[idx].y += add;
             add += diff * (expansion - 1);
         }
-        new_galaxies[last_idx].1 += add;
+        new_galaxies[last_idx].y += add;
         self.galaxies = new_galaxies;
     }
 
     fn max_x(&self) -> isize {
-        self.galaxies.iter().map(|p| p.0).max().unwrap()
+        self.galaxies.iter().map(|p| p.x).max().unwrap()
     }
 
     fn min_x(&self) -> isize {
-        self.galaxies.iter().map(|p| p.0).min().unwrap()
+        self.galaxies.iter().map(|p| p.x).min().unwrap()
     }
 
     fn max_y(&self) -> isize {
-        self.galaxies.iter().map(|p| p.1).max().unwrap()
+        self.galaxies.iter().map(|p| p.y).max().unwrap()
     }
 
     fn min_y(&self) -> isize {
-        self.galaxies.iter().map(|p| p.1).min().unwrap()
+        self.galaxies.iter().map(|p| p.y).min().unwrap()
     }
 }
 
@@ -62,7 +55,7 @@ impl Debug for Space {
         let mut s = String::new();
         for y in self.min_y()..=self.max_y() {
             for x in self.min_x()..=self.max_x() {
-                if self.galaxies.contains(&Pos(x, y)) {
+                if self.galaxies.contains(&Point::new(x, y)) {
                     s.push('#');
                 } else {
                     s.push('.');
@@ -78,69 +71,39 @@ pub struct Day;
 
 impl<'a> Solution<'a> for Day {
     type Input = Space;
-    type Output = isize;
-    const DAY: &'a str = "Day11";
+    type Output1 = isize;
+    type Output2 = isize;
+    const DAY: &'static str = "Day11";
 
-    fn part1(input: &Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
         let mut input = Space {
             galaxies: input.galaxies.clone()
         };
         input.expand(2);
-        input.galaxies.iter().combinations(2)
-            .map(|pair| pair[0].distance(pair[1]))
-            .sum()
+        Ok(input.galaxies.iter().combinations(2)
+            .map(|pair| pair[0].manhattan(pair[1]))
+            .sum())
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
         let mut input = Space {
             galaxies: input.galaxies.clone()
         };
         input.expand(1_000_000);
-        input.galaxies.iter().combinations(2)
-            .map(|pair| pair[0].distance(pair[1]))
-            .sum()
+        Ok(input.galaxies.iter().combinations(2)
+            .map(|pair| pair[0].manhattan(pair[1]))
+            .sum())
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        let mut galaxies = Vec::new();
-        for y in 0..raw_input.len() {
-            for x in 0..raw_input[y].len() {
-                let c = raw_input[y].chars().nth(x).unwrap();
-                if c == '#' {
-                    galaxies.push(Pos(x as isize, y as isize));
-                }
-            }
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        let galaxies = Grid::parse(raw_input, |c| Ok(c == '#'))?
+            .iter()
+            .filter(|(_, galaxy)| **galaxy)
+            .map(|(pos, _)| Point::from(pos))
+            .collect::<Vec<_>>();
+        if galaxies.is_empty() {
+            return Err(AocError::WrongFormat("no galaxies".to_string()));
         }
-        Space { galaxies }
+        Ok(Space { galaxies })
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn get_input() -> Vec<String> {
-        "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....".lines().map(|s| s.to_string()).collect()
-    }
-
-    #[test]
-    fn test_part1() {
-        let input = &Day::parse_input(&get_input());
-        assert_eq!(Day::part1(input), 374);
-    }
-
-    #[test]
-    fn test_part2() {
-        let input = &Day::parse_input(&get_input());
-        assert_eq!(Day::part2(input), 8410);
-    }
-}
\ No newline at end of file
@@ -1,5 +1,6 @@
 use std::str::FromStr;
-use crate::util::Solution;
+use crate::err::{number, AocError};
+use crate::util::{parse_lines, Solution};
 
 
 pub struct Springs {
@@ -8,40 +9,32 @@ pub struct Springs {
 }
 
 impl Springs {
-    fn variants(springs: Springs, vals: usize) -> usize {
-        let statuses = springs.statuses;
-        let groups = springs.groups;
-        if statuses.len() == 0 && groups.len() == 0 {
-            return 1;
-        }
-        if statuses.len() == 0 || groups.len() == 0 {
+    /// Number of ways to fill the unknown springs so the damaged runs match `groups`.
+    /// `Some(true)` is an operational spring and `Some(false)` a damaged one
+    fn variants(statuses: &[Option<bool>], groups: &[u16]) -> usize {
+        let Some((&group, rest)) = groups.split_first() else {
+            return if statuses.contains(&Some(false)) { 0 } else { 1 };
+        };
+        let Some(first) = statuses.first() else {
             return 0;
-        }
-        let group = groups[0];
-        let mut add = 0;
-        if statuses.len() > group as usize && statuses.iter().take(group as usize).all(|s| s.is_none() || *s == Some(false))
-            && statuses.get(group as usize + 1) != Some(&Some(false))
+        };
+        let group = group as usize;
+        let mut count = 0;
+        if statuses.len() >= group && statuses[..group].iter().all(|s| *s != Some(true))
+            && statuses.get(group) != Some(&Some(false))
         {
-            let new_len = std::cmp::min(statuses.len(), group as usize + 1);
-            add += Springs::variants(
-                Springs {
-                    statuses: statuses[new_len..].to_vec(),
-                    groups: groups[1..].to_vec(),
-                }, vals);
+            let next = std::cmp::min(statuses.len(), group + 1);
+            count += Springs::variants(&statuses[next..], rest);
         }
-
-        add += Springs::variants(
-            Springs {
-                statuses: statuses[1..].to_vec(),
-                groups: groups.clone(),
-            }, vals);
-
-        add + vals
+        if *first != Some(false) {
+            count += Springs::variants(&statuses[1..], groups);
+        }
+        count
     }
 }
 
 impl FromStr for Springs {
-    type Err = ();
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let [statuses, groups] = s.split_whitespace().collect::<Vec<&str>>()[..] {
@@ -51,11 +44,10 @@ impl FromStr for Springs {
                 _ => None
             }).collect::<Vec<Option<bool>>>();
             let groups = groups.split(',')
-                .map(|val| val.parse()
-                    .map_err(|_| ())).collect::<Result<Vec<u16>, Self::Err>>()?;
+                .map(number).collect::<Result<Vec<u16>, _>>()?;
             return Ok(Springs { statuses, groups });
         }
-        Err(())
+        Err(AocError::WrongFormat(s.to_string()))
     }
 }
 
@@ -64,26 +56,21 @@ pub struct Day;
 
 impl<'a> Solution<'a> for Day {
     type Input = Vec<Springs>;
-    type Output = ();
-    const DAY: &'a str = "Day12";
+    type Output1 = usize;
+    type Output2 = usize;
+    const DAY: &'static str = "Day12";
 
-    fn part1(input: &Self::Input) -> Self::Output {
-        input.into_iter()
-            .map(|s| Springs {
-                statuses: s.statuses.clone(),
-                groups: s.groups.clone(),
-            })
-            .map(|springs| Springs::variants(springs, 0))
-            .for_each(|v| println!("{}", v));
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
+        Ok(input.iter()
+            .map(|springs| Springs::variants(&springs.statuses, &springs.groups))
+            .sum())
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
-        ()
+    fn part2(_input: &Self::Input) -> Result<Self::Output2, AocError> {
+        Err(AocError::NoSolution("part 2 is not implemented yet".to_string()))
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        raw_input.iter()
-            .map(|line| line.parse().unwrap())
-            .collect()
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        parse_lines(raw_input, Springs::from_str)
     }
 }
@@ -1,5 +1,6 @@
 use std::fmt::{Debug, Formatter};
 use itertools::Itertools;
+use crate::err::AocError;
 use crate::util::Solution;
 
 pub struct Day;
@@ -27,19 +28,18 @@ enum Instruction<'a> {
     Equals(&'a str, u8),
 }
 
-impl<'a> From<&'a str> for Instruction<'a> {
-    fn from(value: &'a str) -> Self {
+impl<'a> TryFrom<&'a str> for Instruction<'a> {
+    type Error = AocError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
         match value {
-            val if val.ends_with('-') => Instruction::Dash(val.strip_suffix('-').unwrap()),
+            val if val.ends_with('-') => Ok(Instruction::Dash(val.strip_suffix('-').unwrap())),
             val if val.contains('=') => {
-                let mut split = val.split('=');
-                let key = split.next().unwrap();
-                let val = split.next().unwrap();
-                Instruction::Equals(key, val.parse().unwrap())
+                let (key, val) = val.split_once('=').unwrap();
+                Ok(Instruction::Equals(key, val.parse()?))
             }
-            _ => panic!("Invalid instruction: {}", value)
+            _ => Err(AocError::Invalid("instruction", value.to_string()))
         }
-
     }
 }
 
@@ -118,29 +118,28 @@ impl Hashable<'_, usize> for HM<'_> {
 
 
 impl<'a> Solution<'a> for Day {
-    type Input = Box<[Box<str>]>;
-    type Output = u32;
-    const DAY: &'a str = "Day15";
+    type Input = Box<[&'a str]>;
+    type Output1 = u32;
+    type Output2 = u32;
+    const DAY: &'static str = "Day15";
 
-    fn part1(input: &Self::Input) -> Self::Output {
-        input.iter()
-            .map(|s| s.as_ref())
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
+        Ok(input.iter()
             .map(|a| a.hash() as u32)
-            .sum::<u32>()
+            .sum::<u32>())
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError> {
         let mut hm = HM::new();
-        input.iter()
-            .map(|s| Instruction::from(s.as_ref()))
-            .for_each(|a| hm.handle(a));
-        hm.hash() as u32
+        for s in input.iter() {
+            hm.handle(Instruction::try_from(*s)?);
+        }
+        Ok(hm.hash() as u32)
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        raw_input[0].split(',')
-            .map(|s| s.to_string())
-            .map(|s| s.into_boxed_str())
-            .collect::<Box<[Box<str>]>>()
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        let sequence = raw_input.lines().next()
+            .ok_or(AocError::WrongFormat("missing initialization sequence".to_string()))?;
+        Ok(sequence.split(',').collect())
     }
 }
\ No newline at end of file
@@ -1,20 +1,9 @@
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
-use std::ops::Deref;
+use crate::commons::{Direction, Grid, Point};
+use crate::err::AocError;
 use crate::util::Solution;
 
-type Pos = (isize, isize);
-
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-    X
-}
-
 #[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
 pub enum Tile {
     Empty,      //# .
@@ -26,48 +15,27 @@ pub enum Tile {
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
 struct Bean {
-    pos: Pos,
+    pos: Point<isize>,
     dir: Direction,
 }
 
-impl Bean {
-    fn next_pos(&self) -> Pos {
-        let (x, y) = self.pos;
-        match self.dir {
-            Direction::Up => (x, y - 1),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-            Direction::Right => (x + 1, y),
-            Direction::X => (x, y),
-        }
-    }
-}
-
 pub struct Cave {
-    tiles: Box<[Box<[Tile]>]>,
+    tiles: Grid<Tile>,
     beans: Vec<Bean>,
     visited: HashSet<Bean>,
 }
 
 impl Debug for Cave {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let (max_x, max_y) = (self.tiles[0].len(), self.tiles.len());
         let mut cave = Cave::from(&self.tiles);
         while cave.step() {}
-        let hs = cave.visited.into_iter()
-            .map(|bean| bean.pos)
-            .collect::<HashSet<Pos>>();
-        for y in 0..max_y {
-            for x in 0..max_x {
-                if hs.contains(&(x as isize, y as isize)) {
-                    write!(f, "#")?;
-                } else {
-                    write!(f, ".")?;
-                }
+        let mut energized = self.tiles.map(|_| '.');
+        for bean in cave.visited {
+            if let Some(pos) = self.tiles.position(bean.pos) {
+                energized[pos] = '#';
             }
-            writeln!(f)?;
         }
-        Ok(())
+        write!(f, "{}", energized)
     }
 }
 
@@ -75,71 +43,31 @@ impl Cave {
     fn step(&mut self) -> bool {
         let mut beans_to_add = Vec::new();
         let mut beans_to_remove = Vec::new();
-        let mut counter = 0;
         for bean in self.beans.iter_mut() {
-            let (x, y) = bean.next_pos();
-            if x < 0 || y < 0
-                || x >= self.tiles[0].len() as isize
-                || y >= self.tiles.len() as isize {
-                beans_to_remove.push(bean.clone());
-                counter += 1;
+            let next = bean.pos.step(bean.dir);
+            let Some(tile) = self.tiles.position(next) else {
+                beans_to_remove.push(*bean);
                 continue;
-            }
-            bean.pos = (x, y);
+            };
+            bean.pos = next;
             if self.visited.contains(bean) {
-                println!("visited {}: {:?}", counter, bean);
-                beans_to_remove.push(bean.clone());
-                counter += 1;
+                beans_to_remove.push(*bean);
                 continue;
             }
-            match self.tiles[y as usize][x as usize] {
-                Tile::FMirror => {
-                    bean.dir = match bean.dir {
-                        Direction::Up => Direction::Right,
-                        Direction::Down => Direction::Left,
-                        Direction::Left => Direction::Down,
-                        Direction::Right => Direction::Up,
-                        Direction::X => Direction::X,
-                    }
-                }
-                Tile::TMirror => {
-                    bean.dir = match bean.dir {
-                        Direction::Up => Direction::Left,
-                        Direction::Down => Direction::Right,
-                        Direction::Left => Direction::Up,
-                        Direction::Right => Direction::Down,
-                        Direction::X => Direction::X,
-                    }
+            match self.tiles[tile] {
+                Tile::FMirror if bean.dir.is_vertical() => bean.dir = bean.dir.turn_right(),
+                Tile::FMirror => bean.dir = bean.dir.turn_left(),
+                Tile::TMirror if bean.dir.is_vertical() => bean.dir = bean.dir.turn_left(),
+                Tile::TMirror => bean.dir = bean.dir.turn_right(),
+                Tile::Vertical if !bean.dir.is_vertical() => {
+                    beans_to_add.push(Bean { pos: next, dir: Direction::Up });
+                    bean.dir = Direction::Down;
                 }
-                Tile::Vertical => {
-                    bean.dir = match bean.dir {
-                        Direction::Up => Direction::Up,
-                        Direction::Down => Direction::Down,
-                        Direction::Left | Direction::Right => {
-                            beans_to_add.push(Bean {
-                                pos: (x, y),
-                                dir: Direction::Up,
-                            });
-                            Direction::Down
-                        },
-                        Direction::X => Direction::X,
-                    }
+                Tile::Horizontal if bean.dir.is_vertical() => {
+                    beans_to_add.push(Bean { pos: next, dir: Direction::Left });
+                    bean.dir = Direction::Right;
                 }
-                Tile::Horizontal => {
-                    bean.dir = match bean.dir {
-                        Direction::Left => Direction::Left,
-                        Direction::Right => Direction::Right,
-                        Direction::Up | Direction::Down => {
-                            beans_to_add.push(Bean {
-                                pos: (x, y),
-                                dir: Direction::Left,
-                            });
-                            Direction::Right
-                        },
-                        Direction::X => Direction::X,
-                    }
-                }
-                Tile::Empty => {}
+                Tile::Vertical | Tile::Horizontal | Tile::Empty => {}
             }
         }
         for bean in beans_to_remove {
@@ -149,25 +77,19 @@ impl Cave {
         }
 
         let visited_len = self.visited.len();
-        self.visited.extend(beans_to_add.iter().map(|bean| Bean {
-            pos: bean.pos,
-            dir: Direction::X,
-        }));
+        self.visited.extend(beans_to_add.iter());
         self.visited.extend(self.beans.iter());
         self.beans.append(&mut beans_to_add);
-        println!("{}/{}; {}->{}", counter, self.beans.len(), visited_len, self.visited.len());
         visited_len != self.visited.len()
     }
 }
 
-impl From<&Box<[Box<[Tile]>]>> for Cave {
-    fn from(value: &Box<[Box<[Tile]>]>) -> Self {
+impl From<&Grid<Tile>> for Cave {
+    fn from(value: &Grid<Tile>) -> Self {
         Cave {
-            tiles: value.iter().map(|item|
-                item.deref().to_vec().into_boxed_slice()
-            ).collect::<Vec<_>>().into_boxed_slice(),
+            tiles: value.clone(),
             beans: vec![Bean {
-                pos: (-1, 0),
+                pos: Point::new(-1, 0),
                 dir: Direction::Right,
             }],
             visited: HashSet::new(),
@@ -178,42 +100,32 @@ impl From<&Box<[Box<[Tile]>]>> for Cave {
 pub struct Day;
 
 impl<'a> Solution<'a> for Day {
-    type Input = Box<[Box<[Tile]>]>;
-    type Output = usize;
-    const DAY: &'a str = "Day16";
+    type Input = Grid<Tile>;
+    type Output1 = usize;
+    type Output2 = usize;
+    const DAY: &'static str = "Day16";
 
-    fn part1(input: &Self::Input) -> Self::Output {
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError> {
         let mut cave = Cave::from(input);
-        println!("{:?}", cave);
-        while cave.step() {
-            // println!("====================");
-        }
-        cave.visited.into_iter()
+        while cave.step() {}
+        Ok(cave.visited.into_iter()
             .map(|bean| bean.pos)
-            .collect::<HashSet<Pos>>()
-            .len()
+            .collect::<HashSet<Point<isize>>>()
+            .len())
     }
 
-    fn part2(input: &Self::Input) -> Self::Output {
-        1
+    fn part2(_input: &Self::Input) -> Result<Self::Output2, AocError> {
+        Err(AocError::NoSolution("part 2 is not implemented yet".to_string()))
     }
 
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input {
-        let mut tiles = Vec::new();
-        for line in raw_input {
-            let mut row = Vec::new();
-            for ch in line.chars() {
-                match ch {
-                    '.' => row.push(Tile::Empty),
-                    '-' => row.push(Tile::Horizontal),
-                    '|' => row.push(Tile::Vertical),
-                    '/' => row.push(Tile::FMirror),
-                    '\\' => row.push(Tile::TMirror),
-                    _ => {}
-                }
-            }
-            tiles.push(row.into_boxed_slice());
-        }
-        tiles.into_boxed_slice()
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        Grid::parse(raw_input, |ch| match ch {
+            '.' => Ok(Tile::Empty),
+            '-' => Ok(Tile::Horizontal),
+            '|' => Ok(Tile::Vertical),
+            '/' => Ok(Tile::FMirror),
+            '\\' => Ok(Tile::TMirror),
+            other => Err(AocError::Invalid("tile", other.to_string())),
+        })
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,5 @@
+use crate::util::DynSolution;
+
 mod day01;
 mod day02;
 mod day03;
@@ -26,4 +28,65 @@ pub use day10::Day as Day10;
 pub use day11::Day as Day11;
 pub use day12::Day as Day12;
 pub use day15::Day as Day15;
-pub use day16::Day as Day16;
\ No newline at end of file
+pub use day16::Day as Day16;
+
+/// Every implemented day, in order
+pub fn registry() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        Box::new(Day01),
+        Box::new(Day02),
+        Box::new(Day03),
+        Box::new(Day04),
+        Box::new(Day05),
+        Box::new(Day06),
+        Box::new(Day07),
+        Box::new(Day08),
+        Box::new(Day09),
+        Box::new(Day10),
+        Box::new(Day11),
+        Box::new(Day12),
+        Box::new(Day15),
+        Box::new(Day16),
+    ]
+}
+
+pub fn find(day: u8) -> Option<Box<dyn DynSolution>> {
+    registry().into_iter().find(|solution| solution.day() == day)
+}
+
+/// Tests generated by `build.rs` from the `examples/dayNN/*.in` and `*.expected` files
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::solve_str;
+
+    #[test]
+    fn test_registry_ordered() {
+        let days = registry().iter().map(|s| s.day()).collect::<Vec<_>>();
+        let mut sorted = days.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(days, sorted);
+    }
+
+    #[test]
+    fn test_find() {
+        let solution = find(1).unwrap();
+        assert_eq!(solution.name(), "Day01");
+        assert_eq!(solution.run_part1("1abc2\npqr3stu8vwx").unwrap(), "50");
+        assert!(find(13).is_none());
+    }
+
+    #[test]
+    fn test_solve_str() {
+        let (part1, part2) = solve_str::<Day06>("Time:      7  15   30\nDistance:  9  40  200").unwrap();
+        assert_eq!((part1, part2), (288, 71503));
+    }
+}
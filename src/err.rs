@@ -1,7 +1,130 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::num::ParseIntError;
+use std::ops::Range;
+use std::str::FromStr;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
-pub enum InputError {
+pub enum AocError {
+    #[error("Cannot read input: {0}")]
+    Io(#[from] io::Error),
     #[error("Invalid input: {0}")]
     WrongFormat(String),
-}
\ No newline at end of file
+    #[error("Invalid {0}: {1}")]
+    Invalid(&'static str, String),
+    #[error("Cannot parse to int: {0}")]
+    ParseInt(#[from] ParseIntError),
+    #[error("{0}")]
+    Parse(Box<ParseError>),
+    #[error("No solution found: {0}")]
+    NoSolution(String),
+    #[error("Cannot parse {0}: {1}")]
+    Config(String, String),
+    #[error("TIMEOUT after {0:.2?}")]
+    Timeout(Duration),
+    #[error("Refusing to overwrite {0}")]
+    Exists(String),
+    #[error("Missing {0}")]
+    Missing(String),
+    #[error("Request failed: {0}")]
+    Http(String),
+    #[error("Refusing to submit {0}")]
+    Refused(String),
+}
+
+/// Parse error pointing at the offending line and columns of the input
+#[derive(Debug)]
+pub struct ParseError {
+    pub message: String,
+    pub file: Option<String>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based, end-exclusive column range of the bad token
+    pub columns: Range<usize>,
+    pub source_line: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, self.file.as_deref().unwrap_or("<input>"),
+                 self.line, self.columns.start)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{} | {}{}", gutter,
+               " ".repeat(self.columns.start - 1),
+               "^".repeat(self.columns.len().max(1)))
+    }
+}
+
+impl AocError {
+    /// Locates the error at the 1-based `line` of the input, pointing at the bad token if it can be found
+    pub fn at(self, line: usize, source: &str) -> AocError {
+        let token = match &self {
+            AocError::WrongFormat(token) | AocError::Invalid(_, token) => Some(token.trim()),
+            AocError::ParseInt(_) => None,
+            AocError::Io(_) | AocError::Parse(_) | AocError::NoSolution(_)
+            | AocError::Config(_, _) | AocError::Timeout(_)
+            | AocError::Exists(_) | AocError::Missing(_) | AocError::Http(_)
+            | AocError::Refused(_) => return self,
+        };
+        let bytes = token
+            .filter(|token| !token.is_empty())
+            .and_then(|token| source.find(token).map(|start| start..start + token.len()))
+            .unwrap_or(0..source.len());
+        let start = source[..bytes.start].chars().count() + 1;
+        let end = start + source[bytes].chars().count();
+        AocError::Parse(Box::new(ParseError {
+            message: self.to_string(),
+            file: None,
+            line,
+            columns: start..end,
+            source_line: source.to_string(),
+        }))
+    }
+
+    /// Records which input file a located parse error came from
+    pub fn in_file(mut self, file: &str) -> AocError {
+        if let AocError::Parse(err) = &mut self {
+            err.file = Some(file.to_string());
+        }
+        self
+    }
+}
+
+/// Parses a number, keeping the token around so the error can be located
+pub fn number<T: FromStr>(token: &str) -> Result<T, AocError> {
+    token.parse().map_err(|_| AocError::Invalid("number", token.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_located() {
+        let err = AocError::Invalid("card", "X".to_string())
+            .at(3, "32TXK 765")
+            .in_file("input/Day07.in");
+        assert_eq!(err.to_string(), "Invalid card: X
+ --> input/Day07.in:3:4
+  |
+3 | 32TXK 765
+  |    ^");
+    }
+
+    #[test]
+    fn test_unlocated_token_spans_line() {
+        let err = AocError::WrongFormat("missing".to_string()).at(12, "abc");
+        match err {
+            AocError::Parse(err) => {
+                assert_eq!(err.line, 12);
+                assert_eq!(err.columns, 1..4);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
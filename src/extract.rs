@@ -0,0 +1,137 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::err::AocError;
+
+pub const EXAMPLES_DIR: &str = "examples";
+
+lazy_static! {
+    static ref ARTICLE: Regex = Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap();
+    static ref PRE_CODE: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    static ref EM_CODE: Regex = Regex::new(r"(?s)<code><em>(.*?)</em></code>").unwrap();
+    static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+}
+
+/// Example input of a puzzle page together with the answers the description gives for it
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Vec<(u8, String)>,
+}
+
+impl Example {
+    /// Contents of the `.expected` file read by `build.rs`
+    pub fn expected(&self) -> String {
+        let mut expected = String::new();
+        for (part, answer) in self.answers.iter() {
+            writeln!(expected, "part{}: {}", part, answer).unwrap();
+        }
+        expected
+    }
+}
+
+/// Strips markup inside a code block and decodes the entities the puzzle pages use
+fn text(html: &str) -> String {
+    TAG.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Extracts examples from a saved puzzle page. Each part's description contributes its first
+/// `<pre><code>` block as the example, answered by the last emphasised `<code><em>` in it.
+/// A part without a block of its own answers the previous example.
+pub fn parse_page(html: &str) -> Result<Vec<Example>, AocError> {
+    let mut examples: Vec<Example> = Vec::new();
+    for (idx, article) in ARTICLE.captures_iter(html).take(2).enumerate() {
+        let part = idx as u8 + 1;
+        let article = &article[1];
+        let input = PRE_CODE.captures(article).map(|code| text(&code[1]));
+        let answer = EM_CODE.captures_iter(article).last()
+            .map(|code| text(&code[1]).trim().to_string())
+            .ok_or_else(|| AocError::Invalid("puzzle page", format!("part {} has no emphasised answer", part)))?;
+        match (input, examples.last_mut()) {
+            (Some(input), Some(last)) if last.input != input => {
+                examples.push(Example { input, answers: vec![(part, answer)] })
+            }
+            (_, Some(last)) => last.answers.push((part, answer)),
+            (Some(input), None) => examples.push(Example { input, answers: vec![(part, answer)] }),
+            (None, None) => return Err(AocError::Invalid("puzzle page", "part 1 has no example".to_string())),
+        }
+    }
+    if examples.is_empty() {
+        return Err(AocError::Invalid("puzzle page", "no puzzle description found".to_string()));
+    }
+    Ok(examples)
+}
+
+/// Writes `example.in` and `example.expected` into `dir`, numbering them when there are several
+/// examples. Existing files are only replaced with `force`
+pub fn write_examples(dir: &Path, examples: &[Example], force: bool) -> Result<Vec<PathBuf>, AocError> {
+    let files = examples.iter().enumerate()
+        .flat_map(|(idx, example)| {
+            let name = match examples.len() {
+                1 => "example".to_string(),
+                _ => format!("example{}", idx + 1),
+            };
+            [
+                (dir.join(format!("{}.in", name)), example.input.clone()),
+                (dir.join(format!("{}.expected", name)), example.expected()),
+            ]
+        })
+        .collect::<Vec<_>>();
+    if let Some((path, _)) = files.iter().find(|(path, _)| !force && path.exists()) {
+        return Err(AocError::Exists(path.display().to_string()));
+    }
+    fs::create_dir_all(dir)?;
+    for (path, content) in files.iter() {
+        fs::write(path, content)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 7: Camel Cards ---</h2><p>For example:</p>
+<pre><code>32T3K 765
+T55J5 684
+</code></pre>
+<p>Hand <code>T55J5</code> ranks <em>3</em>.</p>
+<p>So, the total winnings are <code><em>6440</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>253910319</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Now, the total winnings are <code><em>5905</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_parse_page() {
+        let examples = parse_page(PAGE).unwrap();
+        assert_eq!(examples, vec![Example {
+            input: "32T3K 765\nT55J5 684\n".to_string(),
+            answers: vec![(1, "6440".to_string()), (2, "5905".to_string())],
+        }]);
+        assert_eq!(examples[0].expected(), "part1: 6440\npart2: 5905\n");
+    }
+
+    #[test]
+    fn test_separate_examples() {
+        let page = r#"<article class="day-desc"><pre><code>a&lt;<em>b</em>
+</code></pre><code><em>1</em></code></article>
+<article class="day-desc"><pre><code>c&amp;d
+</code></pre><code><em>2</em></code></article>"#;
+        let examples = parse_page(page).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].input, "a<b\n");
+        assert_eq!(examples[1].input, "c&d\n");
+        assert_eq!(examples[1].answers, vec![(2, "2".to_string())]);
+    }
+}
@@ -1,10 +1,16 @@
-#![feature(test)]
-#[allow(unused_imports)]
-extern crate test;
-
 pub mod days;
 mod util;
 mod err;
-mod commons;
+pub mod commons;
+pub mod runner;
+pub mod answers;
+pub mod alloc;
+pub mod bench;
+pub mod client;
+pub mod extract;
+pub mod scaffold;
+pub mod submit;
+pub mod watch;
 
-pub use util::solve as solve;
\ No newline at end of file
+pub use err::AocError;
+pub use util::{input_path, read_input, solve, solve_file, solve_str, DynSolution, Solution};
\ No newline at end of file
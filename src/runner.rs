@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use crate::alloc::Allocs;
+use crate::err::AocError;
+
+/// Answer of a single part together with how long it took
+#[derive(Debug)]
+pub struct Timed {
+    pub answer: Result<String, AocError>,
+    pub elapsed: Duration,
+}
+
+impl Timed {
+    pub fn measure<F>(f: F) -> Self
+        where F: FnOnce() -> Result<String, AocError> {
+        let start = Instant::now();
+        let answer = f();
+        Timed { answer, elapsed: start.elapsed() }
+    }
+
+    /// Waits at most `timeout` for the next part a watched thread sends over `rx`. A part that
+    /// timed out cannot be stopped, its thread keeps running in the background until the process
+    /// exits
+    pub fn receive(rx: &Receiver<Timed>, timeout: Duration) -> Self {
+        match rx.recv_timeout(timeout) {
+            Ok(timed) => timed,
+            Err(RecvTimeoutError::Timeout) => Timed { answer: Err(AocError::Timeout(timeout)), elapsed: timeout },
+            Err(RecvTimeoutError::Disconnected) => Timed {
+                answer: Err(AocError::NoSolution("part panicked".to_string())),
+                elapsed: Duration::ZERO,
+            },
+        }
+    }
+}
+
+/// Outcome of running one day, parts that were not requested are `None`
+#[derive(Debug)]
+pub struct Run {
+    pub parse: Duration,
+    pub part1: Option<Timed>,
+    pub part2: Option<Timed>,
+    /// Wall-clock time of the whole day, including reading its input
+    pub wall: Duration,
+    /// Allocations made while parsing the input
+    pub parse_allocs: Allocs,
+    /// Allocations that copying the input into one `String` per line would have added, only
+    /// filled in by callers that report them
+    pub line_copy_allocs: Allocs,
+}
+
+impl Run {
+    pub fn parts(&self) -> impl Iterator<Item=(u8, &Timed)> {
+        [(1, &self.part1), (2, &self.part2)].into_iter()
+            .filter_map(|(part, timed)| Some((part, timed.as_ref()?)))
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts().map(|(_, timed)| timed.elapsed).sum::<Duration>()
+    }
+}
+
+/// Machine-readable summary of one day, answers and errors use the same strings as the text output
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Report {
+    pub day: u8,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub part1_ns: Option<u64>,
+    pub part2_ns: Option<u64>,
+    pub wall_ns: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl Report {
+    pub fn new(day: u8, run: &Result<Run, AocError>) -> Self {
+        let nanos = |d: Duration| d.as_nanos() as u64;
+        match run {
+            Ok(run) => {
+                let answer = |timed: &Option<Timed>| timed.as_ref()?.answer.as_ref().ok().cloned();
+                let elapsed = |timed: &Option<Timed>| timed.as_ref().map(|timed| nanos(timed.elapsed));
+                let errors = run.parts()
+                    .filter_map(|(part, timed)| {
+                        Some(format!("part {}: {}", part, timed.answer.as_ref().err()?))
+                    })
+                    .collect::<Vec<_>>();
+                Report {
+                    day,
+                    part1: answer(&run.part1),
+                    part2: answer(&run.part2),
+                    parse_ns: Some(nanos(run.parse)),
+                    part1_ns: elapsed(&run.part1),
+                    part2_ns: elapsed(&run.part2),
+                    wall_ns: Some(nanos(run.wall)),
+                    error: (!errors.is_empty()).then(|| errors.join("; ")),
+                }
+            }
+            Err(err) => Report {
+                day,
+                part1: None,
+                part2: None,
+                parse_ns: None,
+                part1_ns: None,
+                part2_ns: None,
+                wall_ns: None,
+                error: Some(err.to_string()),
+            }
+        }
+    }
+}
+
+fn duration(duration: Option<Duration>) -> String {
+    duration.map(|d| format!("{:.2?}", d)).unwrap_or_else(|| "-".to_string())
+}
+
+/// Runs `f` on every item on up to `jobs` worker threads, `emit` gets the results in the
+/// order of `items` as soon as every earlier item has finished. An item whose `f` panicked is
+/// emitted as an error so the items after it still are
+pub fn run_ordered<T, R, F, E>(items: &[T], jobs: usize, f: F, mut emit: E)
+    where T: Sync, R: Send, F: Fn(&T) -> R + Sync, E: FnMut(&T, Result<R, AocError>) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let tx = tx.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| f(item))).map_err(|payload| {
+                    let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    AocError::NoSolution(format!("panicked: {}", message))
+                });
+                if tx.send((idx, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+        for (idx, result) in rx {
+            pending.insert(idx, result);
+            while let Some(result) = pending.remove(&emitted) {
+                emit(&items[emitted], result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+/// Renders parse, part and wall-clock timings of every run as an aligned table with a grand total
+pub fn timing_table(runs: &[(&str, &Run)]) -> String {
+    let mut rows = vec![["Day", "Parse", "Part 1", "Part 2", "Total", "Wall"].map(String::from)];
+    for (name, run) in runs {
+        rows.push([
+            name.to_string(),
+            duration(Some(run.parse)),
+            duration(run.part1.as_ref().map(|timed| timed.elapsed)),
+            duration(run.part2.as_ref().map(|timed| timed.elapsed)),
+            duration(Some(run.total())),
+            duration(Some(run.wall)),
+        ]);
+    }
+    let total = runs.iter().map(|(_, run)| run.total()).sum::<Duration>();
+    rows.push(["Total".to_string(), String::new(), String::new(), String::new(), duration(Some(total)),
+               String::new()]);
+
+    let widths = (0..6)
+        .map(|col| rows.iter().map(|row| row[col].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+    let mut table = String::new();
+    for row in rows.iter() {
+        let line = row.iter().zip(widths.iter()).enumerate()
+            .map(|(col, (cell, width))| if col == 0 {
+                format!("{:<width$}", cell)
+            } else {
+                format!("{:>width$}", cell)
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(table, "{}", line.trim_end()).unwrap();
+    }
+    table
+}
+
+/// Sums up the parse allocations of every run and an estimate of what borrowing the input saved,
+/// the saving is worked out from the input size rather than measured
+pub fn allocation_summary(runs: &[(&str, &Run)]) -> String {
+    let parse = runs.iter().map(|(_, run)| run.parse_allocs).sum::<Allocs>();
+    let saved = runs.iter().map(|(_, run)| run.line_copy_allocs).sum::<Allocs>();
+    format!("Parsing made {}, borrowing the input saved an estimated {} over copying it line by line\n", parse, saved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_table() {
+        let run = Run {
+            parse: Duration::from_millis(1),
+            part1: Some(Timed { answer: Ok("1".to_string()), elapsed: Duration::from_millis(2) }),
+            part2: None,
+            wall: Duration::from_millis(4),
+            parse_allocs: Allocs { count: 2, bytes: 64 },
+            line_copy_allocs: Allocs { count: 4, bytes: 40 },
+        };
+        assert_eq!(run.total(), Duration::from_millis(3));
+        let table = timing_table(&[("Day01", &run)]);
+        assert_eq!(table, "\
+Day     Parse  Part 1  Part 2   Total    Wall
+Day01  1.00ms  2.00ms       -  3.00ms  4.00ms
+Total                          3.00ms
+");
+        assert_eq!(allocation_summary(&[("Day01", &run), ("Day02", &run)]), "Parsing made 4 allocations \
+(128 bytes), borrowing the input saved an estimated 8 allocations (80 bytes) over copying it line by \
+line\n");
+    }
+
+    #[test]
+    fn test_run_ordered() {
+        let items = (0..20u64).collect::<Vec<_>>();
+        let mut seen = Vec::new();
+        run_ordered(&items, 4, |&n| {
+            thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        }, |&n, square| seen.push((n, square.unwrap())));
+        assert_eq!(seen, items.iter().map(|&n| (n, n * n)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_run_ordered_survives_panics() {
+        let items = (0..6u64).collect::<Vec<_>>();
+        let mut seen = Vec::new();
+        run_ordered(&items, 3, |&n| {
+            assert_ne!(n, 2, "bad item");
+            n
+        }, |&n, result| seen.push((n, result.map_err(|err| err.to_string()))));
+        assert_eq!(seen.len(), 6);
+        assert!(matches!(&seen[2], (2, Err(err)) if err.contains("bad item")));
+        assert_eq!(seen[5], (5, Ok(5)));
+    }
+
+    #[test]
+    fn test_report_json() {
+        let run = Ok(Run {
+            parse: Duration::from_nanos(10),
+            part1: Some(Timed { answer: Ok("42".to_string()), elapsed: Duration::from_nanos(20) }),
+            part2: Some(Timed {
+                answer: Err(AocError::NoSolution("stuck".to_string())),
+                elapsed: Duration::from_nanos(30),
+            }),
+            wall: Duration::from_nanos(70),
+            parse_allocs: Allocs::default(),
+            line_copy_allocs: Allocs::default(),
+        });
+        let json = serde_json::to_string(&Report::new(3, &run)).unwrap();
+        assert_eq!(json, r#"{"day":3,"part1":"42","part2":null,"parse_ns":10,"part1_ns":20,"part2_ns":30,"wall_ns":70,"error":"part 2: No solution found: stuck"}"#);
+    }
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use lazy_static::lazy_static;
+use regex::Regex;
+use crate::err::AocError;
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs");
+const EXPECTED_TEMPLATE: &str = include_str!("../templates/example.expected");
+
+lazy_static! {
+    static ref MOD_LINE: Regex = Regex::new(r"^mod day(\d{2});$").unwrap();
+    static ref USE_LINE: Regex = Regex::new(r"^pub use day(\d{2})::Day as Day\d{2};$").unwrap();
+    static ref REGISTRY_LINE: Regex = Regex::new(r"^\s*Box::new\(Day(\d{2})\),$").unwrap();
+}
+
+/// Source of a new day module with `DAY` set and both parts stubbed out
+pub fn day_module(day: u8) -> String {
+    DAY_TEMPLATE.replace("{{NN}}", &format!("{:02}", day))
+}
+
+/// Inserts `line` among the lines matching `pattern`, keeping them ordered by day. The new line
+/// copies the indentation of the existing ones
+fn insert_sorted(lines: &mut Vec<String>, pattern: &Regex, day: u8, line: &str) -> Result<(), AocError> {
+    let matches = lines.iter().enumerate()
+        .filter_map(|(idx, line)| {
+            let num = pattern.captures(line)?[1].parse::<u8>().ok()?;
+            Some((idx, num))
+        })
+        .collect::<Vec<_>>();
+    let Some(&(first, _)) = matches.first() else {
+        return Err(AocError::WrongFormat(format!("no line like `{}` in days/mod.rs", line)));
+    };
+    if matches.iter().any(|&(_, num)| num == day) {
+        return Err(AocError::Exists(format!("Day{:02} in days/mod.rs", day)));
+    }
+    let idx = matches.iter().rev()
+        .find(|&&(_, num)| num < day)
+        .map_or(first, |&(idx, _)| idx + 1);
+    let indent = lines[first].len() - lines[first].trim_start().len();
+    let line = format!("{}{}", &lines[first][..indent], line);
+    lines.insert(idx, line);
+    Ok(())
+}
+
+/// Adds the `mod` line, the `pub use` line and the registry entry of `day` to `days/mod.rs`
+pub fn register(mod_rs: &str, day: u8) -> Result<String, AocError> {
+    let mut lines = mod_rs.lines().map(str::to_string).collect::<Vec<_>>();
+    insert_sorted(&mut lines, &MOD_LINE, day, &format!("mod day{:02};", day))?;
+    insert_sorted(&mut lines, &USE_LINE, day, &format!("pub use day{:02}::Day as Day{:02};", day, day))?;
+    insert_sorted(&mut lines, &REGISTRY_LINE, day, &format!("Box::new(Day{:02}),", day))?;
+    let mut registered = lines.join("\n");
+    if mod_rs.ends_with('\n') {
+        registered.push('\n');
+    }
+    Ok(registered)
+}
+
+/// Creates the module, an empty input and an example of `day` under `root`, and registers the
+/// module. Nothing is written if any of the files already exists
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, AocError> {
+    let mod_rs = root.join("src/days/mod.rs");
+    let examples = root.join(format!("examples/day{:02}", day));
+    let files = [
+        (root.join(format!("src/days/day{:02}.rs", day)), day_module(day)),
+        (root.join(format!("input/Day{:02}.in", day)), String::new()),
+        (examples.join("example.in"), String::new()),
+        (examples.join("example.expected"), EXPECTED_TEMPLATE.to_string()),
+    ];
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(AocError::Exists(path.display().to_string()));
+    }
+    let registered = register(&fs::read_to_string(&mod_rs)?, day)?;
+
+    fs::create_dir_all(&examples)?;
+    fs::create_dir_all(root.join("input"))?;
+    for (path, content) in files.iter() {
+        fs::write(path, content)?;
+    }
+    fs::write(&mod_rs, registered)?;
+    Ok(files.into_iter().map(|(path, _)| path).chain([mod_rs]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "use crate::util::DynSolution;
+
+mod day01;
+mod day16;
+
+pub use day01::Day as Day01;
+pub use day16::Day as Day16;
+
+pub fn registry() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        Box::new(Day01),
+        Box::new(Day16),
+    ]
+}
+";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(register(MOD_RS, 7).unwrap(), "use crate::util::DynSolution;
+
+mod day01;
+mod day07;
+mod day16;
+
+pub use day01::Day as Day01;
+pub use day07::Day as Day07;
+pub use day16::Day as Day16;
+
+pub fn registry() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        Box::new(Day01),
+        Box::new(Day07),
+        Box::new(Day16),
+    ]
+}
+");
+        let last = register(MOD_RS, 17).unwrap();
+        assert!(last.contains("mod day16;\nmod day17;\n"));
+        assert!(last.contains("        Box::new(Day16),\n        Box::new(Day17),\n    ]"));
+        assert!(matches!(register(MOD_RS, 16), Err(AocError::Exists(_))));
+    }
+
+    #[test]
+    fn test_day_module() {
+        assert!(day_module(17).contains(r#"const DAY: &'static str = "Day17";"#));
+        // a new day starts with an example test that fails until its answer is filled in
+        assert!(EXPECTED_TEMPLATE.lines().any(|line| line == "part1: ?"));
+    }
+}
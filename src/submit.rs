@@ -0,0 +1,203 @@
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use crate::client::{http_error, Client};
+use crate::err::AocError;
+
+pub const GUESSES_PATH: &str = "input/guesses.jsonl";
+
+/// What the site said about a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong without a hint which way
+    Wrong,
+    RateLimited,
+    /// The part was solved before, the answer was not checked
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Reads the verdict out of the page the site answers a submission with
+    pub fn from_page(html: &str) -> Option<Self> {
+        if html.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if html.contains("That's not the right answer") {
+            Some(if html.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if html.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            })
+        } else if html.contains("You gave an answer too recently") {
+            Some(Outcome::RateLimited)
+        } else if html.contains("You don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited => write!(f, "rate-limited, try again later"),
+            Outcome::AlreadySolved => write!(f, "not checked, the part is already solved"),
+        }
+    }
+}
+
+impl Client {
+    /// Submits `answer` as the solution of the given part
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Outcome, AocError> {
+        let url = format!("{}/answer", self.day_url(day));
+        let page = self.post(&url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|err| http_error(&url, err))?
+            .into_string()?;
+        Outcome::from_page(&page)
+            .ok_or_else(|| AocError::Http(format!("{} returned a page without a verdict", url)))
+    }
+}
+
+/// One submitted answer as stored in the guess log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub day: String,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl Guess {
+    pub fn new(day: &str, part: u8, answer: &str, outcome: Outcome) -> Self {
+        Guess {
+            day: day.to_string(),
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+/// Every answer submitted so far, oldest first, stored as one JSON object per line
+#[derive(Debug, Default)]
+pub struct Guesses(Vec<Guess>);
+
+impl Guesses {
+    pub fn parse(s: &str) -> Result<Self, AocError> {
+        s.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line)
+                .map_err(|err| AocError::Config("guesses".to_string(), err.to_string())))
+            .collect::<Result<_, _>>()
+            .map(Guesses)
+    }
+
+    /// Loads the log, a missing file is an empty log
+    pub fn load(path: &str) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(s) => Guesses::parse(&s).map_err(|err| match err {
+                AocError::Config(_, msg) => AocError::Config(path.to_string(), msg),
+                err => err,
+            }),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Guesses::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn append(path: &str, guess: &Guess) -> Result<(), AocError> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(guess).expect("guess should serialise"))?;
+        Ok(())
+    }
+
+    /// Rejects answers that are certain to be wrong: ones already marked wrong, ones outside the
+    /// bounds set by earlier too high or too low guesses, and anything once the part is solved
+    pub fn check(&self, day: &str, part: u8, answer: &str) -> Result<(), AocError> {
+        let refuse = |reason: String| Err(AocError::Refused(format!("{}: {}", answer, reason)));
+        let guesses = self.0.iter()
+            .filter(|guess| guess.day == day && guess.part == part)
+            .collect::<Vec<_>>();
+        if let Some(correct) = guesses.iter().find(|guess| guess.outcome == Outcome::Correct) {
+            return refuse(format!("{} part {} was already solved with {}", day, part, correct.answer));
+        }
+        if guesses.iter().any(|guess| guess.outcome.is_wrong() && guess.answer == answer) {
+            return refuse("it was already rejected".to_string());
+        }
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |outcome| guesses.iter()
+            .filter(move |guess| guess.outcome == outcome)
+            .filter_map(|guess| guess.answer.parse::<i128>().ok());
+        if let Some(high) = bound(Outcome::TooHigh).min().filter(|&high| value >= high) {
+            return refuse(format!("{} was already too high", high));
+        }
+        if let Some(low) = bound(Outcome::TooLow).max().filter(|&low| value <= low) {
+            return refuse(format!("{} was already too low", low));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::tests::serve;
+    use super::*;
+
+    #[test]
+    fn test_outcome_from_page() {
+        assert_eq!(Outcome::from_page("<p>That's the right answer!</p>"), Some(Outcome::Correct));
+        assert_eq!(Outcome::from_page("<p>That's not the right answer; your answer is too low.</p>"),
+                   Some(Outcome::TooLow));
+        assert_eq!(Outcome::from_page("<p>You gave an answer too recently; you have 41s left to wait.</p>"),
+                   Some(Outcome::RateLimited));
+        assert_eq!(Outcome::from_page("<p>Hello</p>"), None);
+    }
+
+    #[test]
+    fn test_check_bounds() {
+        let guesses = Guesses(vec![
+            Guess::new("Day07", 1, "500", Outcome::TooHigh),
+            Guess::new("Day07", 1, "100", Outcome::TooLow),
+            Guess::new("Day07", 1, "300", Outcome::Wrong),
+            Guess::new("Day07", 1, "250", Outcome::RateLimited),
+            Guess::new("Day07", 2, "7", Outcome::Correct),
+        ]);
+        assert!(guesses.check("Day07", 1, "250").is_ok());
+        assert!(guesses.check("Day07", 1, "300").is_err());
+        assert!(guesses.check("Day07", 1, "600").is_err());
+        assert!(guesses.check("Day07", 1, "100").is_err());
+        assert!(guesses.check("Day07", 2, "8").is_err());
+        assert!(guesses.check("Day08", 1, "600").is_ok());
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = serve("200 OK", "<article><p>That's not the right answer; your answer is too high.</p></article>");
+        let outcome = Client::new(&url, "secret").submit(7, 2, "6440").unwrap();
+        let request = server.join().unwrap();
+        assert_eq!(outcome, Outcome::TooHigh);
+        assert!(request.starts_with("POST /2023/day/7/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=6440"));
+    }
+}
@@ -1,54 +1,247 @@
+use std::fmt::Display;
 use std::io;
-use std::fs::File;
-use std::path::Path;
-use std::io::BufRead;
+use std::fs;
+use std::io::Read;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::alloc::{self, Allocs};
+use crate::bench::{self, Config, Stats};
+use crate::err::AocError;
+use crate::runner::{Run, Timed};
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-    where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+pub fn input_path(day: &str) -> String {
+    format!("input/{}.in", day)
 }
 
-
-pub fn parse(path: &str) -> Vec<String> {
-    match read_lines(path) {
-        Ok(lines) =>
-            lines.map(|res| res.unwrap()).into_iter().collect::<Vec<_>>(),
-        Err(err) => panic!("Cannot read file: {}", err.to_string())
+/// Reads the whole puzzle input from `path`, or from stdin when `path` is `-`
+pub fn read_input(path: &str) -> Result<String, AocError> {
+    if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        return Ok(input);
     }
+    Ok(fs::read_to_string(path)?)
+}
+
+/// Parses every line with `f`, locating any error at the line that caused it
+pub fn parse_lines<'a, T, F>(raw_input: &'a str, f: F) -> Result<Vec<T>, AocError>
+    where F: Fn(&'a str) -> Result<T, AocError> {
+    raw_input.lines().enumerate()
+        .map(|(idx, line)| f(line).map_err(|err| err.at(idx + 1, line)))
+        .collect()
 }
 
+/// A day's puzzle, `Input` may borrow from the raw input it was parsed from for `'a`, each part
+/// answers with whatever type displays it best
 pub trait Solution<'a> {
     type Input;
-    type Output;
-    const DAY: &'a str;
+    type Output1: Display;
+    type Output2: Display;
+    const DAY: &'static str;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError>;
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError>;
+}
+
+/// Object-safe view of a [`Solution`], so days can be stored and looked up at runtime
+pub trait DynSolution: Send + Sync {
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+    fn run_part1(&self, input: &str) -> Result<String, AocError>;
+    fn run_part2(&self, input: &str) -> Result<String, AocError>;
+    /// Parses `input` once and runs the requested parts, timing every step. With a `timeout`
+    /// parsing and the parts run on a watched thread, each of them times out once it takes longer
+    /// than `timeout` and the parts after a timed out one are not run
+    fn run(&self, input: &str, part: Option<u8>, timeout: Option<Duration>) -> Result<Run, AocError>;
+    /// Parses `input` once and benchmarks the given part on it
+    fn bench(&self, input: &str, part: u8, config: &Config) -> Result<Stats, AocError>;
+}
+
+fn run_part<'a, S: Solution<'a>>(input: &S::Input, part: u8) -> Result<String, AocError> {
+    match part {
+        1 => Ok(S::part1(input)?.to_string()),
+        _ => Ok(S::part2(input)?.to_string()),
+    }
+}
+
+/// Parses `input` and answers `parts` on it one after another, `parsed` hears how parsing went
+/// before any part runs
+fn answer_parts<'a, S: Solution<'a>>(input: &'a str, parts: &[u8],
+                                     parsed: impl FnOnce(Result<(Duration, Allocs), AocError>),
+                                     mut answered: impl FnMut(Timed)) {
+    let start = Instant::now();
+    let (input, allocs) = alloc::count(|| S::parse_input(input));
+    match input {
+        Ok(input) => {
+            parsed(Ok((start.elapsed(), allocs)));
+            for &part in parts {
+                answered(Timed::measure(|| run_part::<S>(&input, part)));
+            }
+        }
+        Err(err) => parsed(Err(err)),
+    }
+}
+
+impl<S, O1, O2> DynSolution for S
+    where S: for<'a> Solution<'a, Output1=O1, Output2=O2> + Send + Sync + 'static, O1: Display, O2: Display {
+    fn day(&self) -> u8 {
+        S::DAY.trim_start_matches("Day").parse().expect("DAY should be named DayNN")
+    }
+
+    fn name(&self) -> &'static str {
+        S::DAY
+    }
+
+    fn run_part1(&self, input: &str) -> Result<String, AocError> {
+        run_part::<S>(&S::parse_input(input)?, 1)
+    }
+
+    fn run_part2(&self, input: &str) -> Result<String, AocError> {
+        run_part::<S>(&S::parse_input(input)?, 2)
+    }
+
+    fn run(&self, input: &str, part: Option<u8>, timeout: Option<Duration>) -> Result<Run, AocError> {
+        let start = Instant::now();
+        let parts = [1, 2].into_iter().filter(|p| part.is_none_or(|only| only == *p)).collect::<Vec<u8>>();
+        let mut answers = Vec::new();
+        let (parse, parse_allocs) = match timeout {
+            None => {
+                let mut parsed = None;
+                answer_parts::<S>(input, &parts, |result| parsed = Some(result), |timed| answers.push(timed));
+                parsed.expect("parsing is always reported")?
+            }
+            Some(timeout) => {
+                // a part that times out outlives this call, so the watched thread parses its own
+                // copy of the input once for every part
+                let input: Arc<str> = Arc::from(input);
+                let (parsed_tx, parsed_rx) = mpsc::channel();
+                let (tx, rx) = mpsc::channel();
+                let watched = parts.clone();
+                thread::spawn(move || answer_parts::<S>(&input, &watched,
+                    |result| { let _ = parsed_tx.send(result); },
+                    |timed| { let _ = tx.send(timed); }));
+                let parsed = match parsed_rx.recv_timeout(timeout) {
+                    Ok(parsed) => parsed,
+                    Err(RecvTimeoutError::Timeout) => Err(AocError::Timeout(timeout)),
+                    Err(RecvTimeoutError::Disconnected) => Err(AocError::NoSolution("parsing panicked".to_string())),
+                }?;
+                for part in parts.iter() {
+                    let timed = match answers.last() {
+                        Some(Timed { answer: Err(AocError::Timeout(_)), .. }) => Timed {
+                            answer: Err(AocError::NoSolution(format!("part {} not run after a timeout", part))),
+                            elapsed: Duration::ZERO,
+                        },
+                        _ => Timed::receive(&rx, timeout),
+                    };
+                    answers.push(timed);
+                }
+                parsed
+            }
+        };
+        let mut answers = parts.into_iter().zip(answers).peekable();
+        let mut take = |part: u8| answers.next_if(|(p, _)| *p == part).map(|(_, timed)| timed);
+        let (part1, part2) = (take(1), take(2));
+        Ok(Run { parse, part1, part2, wall: start.elapsed(), parse_allocs,
+            line_copy_allocs: Allocs::default() })
+    }
+
+    fn bench(&self, input: &str, part: u8, config: &Config) -> Result<Stats, AocError> {
+        let input = S::parse_input(input)?;
+        match part {
+            1 => {
+                S::part1(&input)?;
+                Ok(bench::bench(config, || S::part1(&input)))
+            }
+            _ => {
+                S::part2(&input)?;
+                Ok(bench::bench(config, || S::part2(&input)))
+            }
+        }
+    }
+}
+
+pub fn solve<S, O1, O2>() -> Result<(O1, O2), AocError>
+    where S: for<'a> Solution<'a, Output1=O1, Output2=O2> {
+    solve_file::<S, O1, O2>(&input_path(S::DAY))
+}
 
-    fn part1(input: &Self::Input) -> Self::Output;
-    fn part2(input: &Self::Input) -> Self::Output;
-    fn parse_input(raw_input: &Vec<String>) -> Self::Input;
+/// Solves the input stored at `path`, `-` reads it from stdin
+pub fn solve_file<S, O1, O2>(path: &str) -> Result<(O1, O2), AocError>
+    where S: for<'a> Solution<'a, Output1=O1, Output2=O2> {
+    let input = read_input(path)?;
+    let file = if path == "-" { "<stdin>" } else { path };
+    solve_str::<S>(&input).map_err(|err| err.in_file(file))
 }
 
-pub fn solve<'a, S: Solution<'a>>() -> (S::Output, S::Output) {
-    let raw_input = parse(format!("input/{}.in", S::DAY).as_str());
-    let input = S::parse_input(&raw_input);
-    (S::part1(&input), S::part2(&input))
+/// Solves an input that is already in memory, without touching the filesystem
+pub fn solve_str<'a, S: Solution<'a>>(input: &'a str) -> Result<(S::Output1, S::Output2), AocError> {
+    let input = S::parse_input(input)?;
+    Ok((S::part1(&input)?, S::part2(&input)?))
 }
 
+/// Registers both parts of a day with the benchmark harness, under `cargo test` they run once
+/// as a smoke test, `aoc-2023 bench <day>` takes the real measurements
 #[macro_export]
 macro_rules! bench_day {
     ($day:ident) => {
-        #[bench]
-        fn bench_code1(b: &mut Bencher) {
-            let input = parse(format!("input/{}.in", $day::DAY).as_str());
-            let input = $day::parse_input(&input);
-            b.iter(|| $day::part1(&input));
+        #[test]
+        fn bench_code1() {
+            $crate::bench::bench_day(&$day, 1, &$crate::bench::Config::smoke()).unwrap();
         }
 
-        #[bench]
-        fn bench_code2(b: &mut Bencher) {
-            let input = parse(format!("input/{}.in", $day::DAY).as_str());
-            let input = $day::parse_input(&input);
-            b.iter(|| $day::part2(&input));
+        #[test]
+        fn bench_code2() {
+            $crate::bench::bench_day(&$day, 2, &$crate::bench::Config::smoke()).unwrap();
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use super::*;
+
+    struct Slow;
+
+    impl<'a> Solution<'a> for Slow {
+        type Input = &'a str;
+        type Output1 = u64;
+        type Output2 = u64;
+        const DAY: &'static str = "Day99";
+
+        fn part1(_input: &Self::Input) -> Result<Self::Output1, AocError> {
+            thread::sleep(Duration::from_millis(200));
+            Ok(1)
+        }
+
+        fn part2(_input: &Self::Input) -> Result<Self::Output2, AocError> {
+            Ok(2)
+        }
+
+        fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+            if raw_input == "hang" {
+                thread::sleep(Duration::from_millis(200));
+            }
+            Ok(raw_input)
+        }
+    }
+
+    #[test]
+    fn test_run_timeout() {
+        let timeout = Some(Duration::from_millis(20));
+        let run = Slow.run("", None, timeout).unwrap();
+        assert!(matches!(run.part1.unwrap().answer, Err(AocError::Timeout(_))));
+        match run.part2.unwrap().answer {
+            Err(AocError::NoSolution(message)) => assert_eq!(message, "part 2 not run after a timeout"),
+            other => panic!("unexpected answer: {:?}", other),
+        }
+        assert!(matches!(Slow.run("hang", None, timeout), Err(AocError::Timeout(_))));
+
+        let run = Slow.run("", Some(2), timeout).unwrap();
+        assert!(run.part1.is_none());
+        assert_eq!(run.part2.unwrap().answer.unwrap(), "2");
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use crate::err::AocError;
+use crate::runner::Report;
+
+/// Modification time and length of every watched file, directories stand for the files in them
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, (SystemTime, u64)>);
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf]) -> Self {
+        let mut files = BTreeMap::new();
+        for path in paths {
+            let entries = match fs::read_dir(path) {
+                Ok(dir) => dir.filter_map(|entry| Some(entry.ok()?.path())).collect(),
+                Err(_) => vec![path.clone()],
+            };
+            for file in entries {
+                match fs::metadata(&file) {
+                    Ok(meta) if meta.is_file() => {
+                        files.insert(file, (meta.modified().unwrap_or(SystemTime::UNIX_EPOCH), meta.len()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Snapshot(files)
+    }
+
+    /// Files that were added, removed or modified since `earlier`
+    pub fn changes(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let mut changed = self.0.iter()
+            .filter(|(file, stamp)| earlier.0.get(*file) != Some(stamp))
+            .map(|(file, _)| file.clone())
+            .chain(earlier.0.keys().filter(|file| !self.0.contains_key(*file)).cloned())
+            .collect::<Vec<_>>();
+        changed.sort();
+        changed
+    }
+}
+
+/// Files `watch` polls for a day named like `Solution::DAY`
+pub fn watched_paths(name: &str) -> Vec<PathBuf> {
+    let lower = name.to_lowercase();
+    vec![
+        PathBuf::from(format!("src/days/{}.rs", lower)),
+        PathBuf::from(format!("input/{}.in", name)),
+        PathBuf::from(format!("examples/{}", lower)),
+    ]
+}
+
+/// Describes how the answers in `report` differ from the `previous` run
+pub fn diff_answers(name: &str, previous: Option<&Report>, report: &Report) -> Vec<String> {
+    let answers = |report: &Report| [report.part1.clone(), report.part2.clone()];
+    let before = previous.map_or([None, None], answers);
+    let mut lines = answers(report).into_iter().zip(before).enumerate()
+        .map(|(idx, (now, before))| {
+            let show = |answer: &Option<String>| answer.clone().unwrap_or_else(|| "-".to_string());
+            let status = match (previous, now == before) {
+                (None, _) => String::new(),
+                (Some(_), true) => " (unchanged)".to_string(),
+                (Some(_), false) => format!(" (was {})", show(&before)),
+            };
+            format!("{} part {}: {}{}", name, idx + 1, show(&now), status)
+        })
+        .collect::<Vec<_>>();
+    if let Some(error) = &report.error {
+        lines.push(format!("{}: {}", name, error));
+    }
+    lines
+}
+
+/// How the watched day is rebuilt and re-run
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub interval: Duration,
+    pub release: bool,
+    pub timeout_ms: Option<u64>,
+}
+
+/// Rebuilds and runs the day through cargo, build output is only shown when it fails
+fn run_day(day: u8, options: &Options) -> Result<Report, AocError> {
+    let mut command = Command::new("cargo");
+    command.args(["run", "--quiet"]);
+    if options.release {
+        command.arg("--release");
+    }
+    command.args(["--", &day.to_string(), "--format", "json"]);
+    if let Some(timeout) = options.timeout_ms {
+        command.args(["--timeout-ms", &timeout.to_string()]);
+    }
+    let output = command.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stdout.lines().last().and_then(|line| serde_json::from_str(line).ok()) {
+        Some(report) => Ok(report),
+        None => {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            Err(AocError::NoSolution("the build or run failed".to_string()))
+        }
+    }
+}
+
+/// Runs the unit and example tests of the day, their output is only shown when they fail
+fn test_day(name: &str) -> Result<bool, AocError> {
+    let output = Command::new("cargo")
+        .args(["test", "--quiet", "--lib", &name.to_lowercase()])
+        .output()?;
+    if !output.status.success() {
+        eprint!("{}{}", String::from_utf8_lossy(&output.stderr), String::from_utf8_lossy(&output.stdout));
+    }
+    Ok(output.status.success())
+}
+
+/// Re-runs the day and its tests every time one of its files changes, never returns
+pub fn watch(day: u8, name: &str, options: &Options) -> Result<(), AocError> {
+    let paths = watched_paths(name);
+    let mut snapshot = Snapshot::take(&paths);
+    let mut previous: Option<Report> = None;
+    let mut changed = Vec::new();
+    loop {
+        if !changed.is_empty() {
+            let files = changed.iter().map(|file: &PathBuf| file.display().to_string()).collect::<Vec<_>>();
+            println!("\nChanged: {}", files.join(", "));
+        }
+        match run_day(day, options) {
+            Ok(report) => {
+                diff_answers(name, previous.as_ref(), &report).iter().for_each(|line| println!("{}", line));
+                previous = Some(report);
+            }
+            Err(err) => eprintln!("{}: {}", name, err),
+        }
+        if test_day(name)? {
+            println!("{} tests passed", name);
+        } else {
+            println!("{} tests FAILED", name);
+        }
+        println!("Watching {}", paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "));
+        changed = loop {
+            thread::sleep(options.interval);
+            let next = Snapshot::take(&paths);
+            let changes = next.changes(&snapshot);
+            snapshot = next;
+            if !changes.is_empty() {
+                break changes;
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc-2023-{}-watch", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("Day16.in");
+        fs::write(&input, "a").unwrap();
+        let paths = [input.clone(), dir.join("examples")];
+
+        let before = Snapshot::take(&paths);
+        assert!(Snapshot::take(&paths).changes(&before).is_empty());
+        fs::write(&input, "ab").unwrap();
+        fs::create_dir(dir.join("examples")).unwrap();
+        fs::write(dir.join("examples").join("example.in"), "").unwrap();
+        assert_eq!(Snapshot::take(&paths).changes(&before),
+                   vec![input.clone(), dir.join("examples").join("example.in")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_diff_answers() {
+        let report = |part1: &str, part2: Option<&str>| Report {
+            day: 16,
+            part1: Some(part1.to_string()),
+            part2: part2.map(str::to_string),
+            parse_ns: None,
+            part1_ns: None,
+            part2_ns: None,
+            wall_ns: None,
+            error: None,
+        };
+        let first = report("46", None);
+        assert_eq!(diff_answers("Day16", None, &first), vec!["Day16 part 1: 46", "Day16 part 2: -"]);
+        assert_eq!(diff_answers("Day16", Some(&first), &report("46", Some("51"))),
+                   vec!["Day16 part 1: 46 (unchanged)", "Day16 part 2: 51 (was -)"]);
+    }
+}
@@ -0,0 +1,23 @@
+use crate::err::AocError;
+use crate::util::Solution;
+
+pub struct Day;
+
+impl<'a> Solution<'a> for Day {
+    type Input = Vec<&'a str>;
+    type Output1 = u64;
+    type Output2 = u64;
+    const DAY: &'static str = "Day{{NN}}";
+
+    fn part1(_input: &Self::Input) -> Result<Self::Output1, AocError> {
+        Err(AocError::NoSolution("part 1 is not implemented yet".to_string()))
+    }
+
+    fn part2(_input: &Self::Input) -> Result<Self::Output2, AocError> {
+        Err(AocError::NoSolution("part 2 is not implemented yet".to_string()))
+    }
+
+    fn parse_input(raw_input: &'a str) -> Result<Self::Input, AocError> {
+        Ok(raw_input.lines().collect())
+    }
+}